@@ -38,6 +38,8 @@ pub enum RootCommand {
     /// Format Rhai source code.
     #[clap(visible_aliases = &["format"])]
     Fmt(FmtCommand),
+    /// Check Rhai source code for errors.
+    Check(CheckCommand),
 }
 
 #[derive(Clone, Subcommand)]
@@ -75,13 +77,42 @@ pub struct FmtCommand {
     #[clap(long)]
     pub check: bool,
 
+    /// Dry-run and print a unified diff for each file
+    /// that is not correctly formatted.
+    #[clap(long)]
+    pub diff: bool,
+
+    /// The format of the reported problems.
+    #[clap(long, default_value = "human")]
+    pub output_format: OutputFormat,
+
     /// Optional pattern to search for files.
     ///
     /// If not provided, it will be determined by
     /// the configuration.
+    ///
+    /// If "-" is given, the source is read from the standard
+    /// input and the formatted source is written to the standard output.
     pub files: Option<String>,
 }
 
+#[derive(Clone, Parser)]
+pub struct CheckCommand {
+    /// The format of the reported problems.
+    #[clap(long, default_value = "human")]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable output with source snippets.
+    Human,
+    /// JSON output for other tools.
+    Json,
+    /// SARIF output for code scanning tools.
+    Sarif,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 pub enum Colors {
     /// Determine whether to colorize output automatically.
@@ -0,0 +1,204 @@
+use anyhow::{anyhow, Context};
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label, Severity},
+    files::SimpleFiles,
+};
+use itertools::Itertools;
+use rhai_common::{config::LintLevel, environment::Environment, util::Normalize};
+use rhai_hir::{error::ErrorKind, source::Source, Hir, Symbol};
+use rhai_rowan::{parser::ParseError, util::is_rhai_def, Parser};
+use std::collections::HashMap;
+use url::Url;
+
+use crate::{args::CheckCommand, parse_error_diagnostic, std_range, Rhai};
+
+struct CheckedFile {
+    file_id: usize,
+    url: Url,
+    parse_errors: Vec<ParseError>,
+}
+
+impl<E: Environment> Rhai<E> {
+    pub async fn execute_check(&mut self, cmd: CheckCommand) -> Result<(), anyhow::Error> {
+        let cwd = self
+            .env
+            .cwd()
+            .context("invalid working directory")?
+            .normalize();
+
+        self.config.prepare(&self.env, &cwd)?;
+
+        let mut files = SimpleFiles::new();
+        let mut sources = Vec::new();
+
+        for path in self.collect_files(&cwd, &self.config, true).await? {
+            let bytes = match self.env.read_file(&path).await {
+                Ok(f) => f,
+                Err(error) => {
+                    tracing::warn!(?path, %error, "failed to read file");
+                    continue;
+                }
+            };
+
+            let src = match String::from_utf8(bytes) {
+                Ok(src) => src,
+                Err(error) => {
+                    tracing::warn!(?path, %error, "invalid source code");
+                    continue;
+                }
+            };
+
+            let url = Url::parse(&format!("file://{}", path.to_string_lossy()))?.normalize();
+            sources.push((
+                url,
+                is_rhai_def(&src),
+                files.add(path.to_string_lossy().into_owned(), src),
+            ));
+        }
+
+        let mut hir = Hir::new();
+        hir.set_import_resolver(self.config.modules.module_resolver());
+        let mut checked_files = Vec::with_capacity(sources.len());
+
+        // Definitions are added first, so that scripts
+        // are parsed with the operators they declare.
+        sources.sort_by_key(|&(_, is_def, _)| !is_def);
+
+        for (url, is_def, file_id) in sources {
+            let src = files.get(file_id)?.source();
+            let parser = Parser::new(src).with_operators(hir.parser_operators());
+
+            let parse = if is_def {
+                parser.parse_def()
+            } else {
+                parser.parse_script()
+            };
+
+            hir.add_source(&url, &parse.clone_syntax());
+
+            checked_files.push(CheckedFile {
+                file_id,
+                url,
+                parse_errors: parse.errors,
+            });
+        }
+
+        hir.resolve_all();
+
+        let file_ids = checked_files
+            .iter()
+            .filter_map(|file| Some((hir.source_of(&file.url)?, file.file_id)))
+            .collect::<HashMap<Source, usize>>();
+
+        let mut diagnostics = Vec::new();
+
+        for file in &checked_files {
+            if !file.parse_errors.is_empty() {
+                for error in file.parse_errors.iter().unique_by(|error| error.range) {
+                    let severity = match self.config.lint.level_for_syntax_error(&error.kind) {
+                        LintLevel::Allow => continue,
+                        LintLevel::Warn => Severity::Warning,
+                        LintLevel::Error => Severity::Error,
+                    };
+
+                    let mut diagnostic = parse_error_diagnostic(file.file_id, error);
+                    diagnostic.severity = severity;
+                    diagnostics.push(diagnostic);
+                }
+
+                // Semantic errors are not reliable
+                // in files with syntax errors.
+                continue;
+            }
+
+            let Some(source) = hir.source_of(&file.url) else {
+                continue;
+            };
+
+            for error in hir.errors_for_source(source) {
+                let diagnostic = match self.config.lint.level_for(&error.kind) {
+                    LintLevel::Allow => continue,
+                    LintLevel::Warn => Diagnostic::warning(),
+                    LintLevel::Error => Diagnostic::error(),
+                };
+
+                let mut labels = Vec::new();
+                labels.extend(
+                    hir.error_range(&error)
+                        .map(|range| Label::primary(file.file_id, std_range(range))),
+                );
+
+                for (symbol, message) in related_symbols(&error.kind) {
+                    labels.extend(
+                        symbol_label(&hir, &file_ids, symbol)
+                            .map(|label| label.with_message(message)),
+                    );
+                }
+
+                diagnostics.push(
+                    diagnostic
+                        .with_code(error.kind.code())
+                        .with_message(error.to_string())
+                        .with_labels(labels),
+                );
+            }
+        }
+
+        self.print_diagnostics(&files, &diagnostics, cmd.output_format)
+            .await?;
+
+        let errors = diagnostics
+            .iter()
+            .filter(|diag| diag.severity == Severity::Error)
+            .count();
+        let warnings = diagnostics.len() - errors;
+
+        tracing::info!(
+            files = checked_files.len(),
+            errors,
+            warnings,
+            "checked files"
+        );
+
+        if errors == 0 {
+            Ok(())
+        } else {
+            Err(anyhow!("some files contained errors"))
+        }
+    }
+}
+
+fn symbol_label(
+    hir: &Hir,
+    file_ids: &HashMap<Source, usize>,
+    symbol: Symbol,
+) -> Option<Label<usize>> {
+    let symbol_data = hir.symbol(symbol)?;
+    let file_id = *file_ids.get(&symbol_data.source.source?)?;
+    let range = std_range(symbol_data.selection_or_text_range()?);
+
+    Some(Label::secondary(file_id, range))
+}
+
+/// Other symbols that help explaining the error.
+fn related_symbols(kind: &ErrorKind) -> Vec<(Symbol, &'static str)> {
+    match kind {
+        ErrorKind::DuplicateFnParameter {
+            existing_symbol, ..
+        } => Vec::from([(*existing_symbol, "parameter with the same name")]),
+        ErrorKind::ArgumentCountMismatch { function, .. } => {
+            Vec::from([(*function, "function declared here")])
+        }
+        ErrorKind::ArgumentTypeMismatch { param, .. } => {
+            Vec::from([(*param, "parameter declared here")])
+        }
+        ErrorKind::ReturnTypeMismatch { declaration, .. } => {
+            Vec::from([(*declaration, "return type declared here")])
+        }
+        ErrorKind::CircularImport { cycle, .. } => cycle
+            .iter()
+            .map(|&import| (import, "next import in the cycle"))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
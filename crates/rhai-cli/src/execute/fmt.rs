@@ -1,11 +1,24 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context};
-use codespan_reporting::files::SimpleFile;
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label},
+    files::SimpleFiles,
+};
 use rhai_common::{environment::Environment, util::Normalize};
 use rhai_fmt::format_syntax;
+use rhai_hir::Hir;
+use rhai_rowan::parser::Parse;
+use similar::TextDiff;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-use crate::{args::FmtCommand, Rhai};
+use crate::{
+    args::{FmtCommand, OutputFormat},
+    parse_error_diagnostics, Rhai,
+};
+
+/// The file name used for the standard input in reports.
+const STDIN_NAME: &str = "<stdin>";
 
 impl<E: Environment> Rhai<E> {
     pub async fn execute_fmt(&mut self, cmd: FmtCommand) -> Result<(), anyhow::Error> {
@@ -15,8 +28,33 @@ impl<E: Environment> Rhai<E> {
             .context("invalid working directory")?
             .normalize();
 
+        let is_stdin = cmd.files.as_deref() == Some("-");
+
+        // Reports are written to the standard output,
+        // nothing else must be written there.
+        if cmd.output_format != OutputFormat::Human {
+            if cmd.diff {
+                return Err(anyhow!(
+                    "`--diff` cannot be used with a json or sarif output format"
+                ));
+            }
+
+            if is_stdin && !cmd.check {
+                return Err(anyhow!(
+                    "formatting the standard input requires `--check` with a json or sarif output format"
+                ));
+            }
+        }
+
         let hir = self.load_hir(&cwd).await?;
 
+        let mut format_opts = rhai_fmt::Options::default();
+        format_opts.update(self.config.fmt.options.clone());
+
+        if is_stdin {
+            return self.format_stdin(&hir, &cmd, format_opts).await;
+        }
+
         if let Some(mut files) = cmd.files {
             if self.env.is_dir(Path::new(&files)) {
                 files = PathBuf::from(files)
@@ -39,30 +77,21 @@ impl<E: Environment> Rhai<E> {
 
         let mut result = Ok(());
 
-        let mut format_opts = rhai_fmt::Options::default();
-        format_opts.update(self.config.fmt.options.clone());
+        let mut source_files = SimpleFiles::new();
+        let mut diagnostics = Vec::new();
 
         for path in files {
             let f = self.env.read_file(&path).await?;
             let source = String::from_utf8_lossy(&f).into_owned();
 
-            let parser = rhai_rowan::Parser::new(&source).with_operators(hir.parser_operators());
-
-            let p = if rhai_rowan::util::is_rhai_def(&source) {
-                parser.parse_def()
-            } else {
-                parser.parse_script()
-            };
+            let p = parse(&hir, &source);
 
             if !p.errors.is_empty() {
-                self.print_parse_errors(
-                    &SimpleFile::new(&*path.to_string_lossy(), source.as_str()),
-                    &p.errors,
-                )
-                .await?;
+                let file_id = source_files.add(path.to_string_lossy().into_owned(), source.clone());
+                diagnostics.extend(parse_error_diagnostics(file_id, &p.errors));
 
                 if !cmd.force {
-                    if cmd.check {
+                    if cmd.check || cmd.diff {
                         result = Err(anyhow!("some files had syntax errors"));
                     } else {
                         result = Err(anyhow!(
@@ -77,8 +106,23 @@ impl<E: Environment> Rhai<E> {
             let formatted = format_syntax(p.into_syntax(), format_opts.clone());
 
             if source != formatted {
-                if cmd.check {
-                    tracing::error!(?path, "the file is not properly formatted");
+                if cmd.diff {
+                    let name = path.to_string_lossy();
+                    let mut stdout = self.env.stdout();
+                    stdout
+                        .write_all(unified_diff(&name, &source, &formatted).as_bytes())
+                        .await?;
+                    stdout.flush().await?;
+
+                    result = Err(anyhow!("some files were not properly formatted"));
+                } else if cmd.check {
+                    if cmd.output_format == OutputFormat::Human {
+                        tracing::error!(?path, "the file is not properly formatted");
+                    } else {
+                        let file_id = source_files.add(path.to_string_lossy().into_owned(), source);
+                        diagnostics.push(unformatted_diagnostic(file_id));
+                    }
+
                     result = Err(anyhow!("some files were not properly formatted"));
                 } else {
                     self.env.write_file(&path, formatted.as_bytes()).await?;
@@ -86,6 +130,93 @@ impl<E: Environment> Rhai<E> {
             }
         }
 
+        self.print_diagnostics(&source_files, &diagnostics, cmd.output_format)
+            .await?;
+
         result
     }
+
+    /// Format the standard input and write the result to the standard output.
+    ///
+    /// With `--check` nothing is written, and with `--diff`
+    /// only the differences are written.
+    async fn format_stdin(
+        &self,
+        hir: &Hir,
+        cmd: &FmtCommand,
+        format_opts: rhai_fmt::Options,
+    ) -> Result<(), anyhow::Error> {
+        let mut bytes = Vec::new();
+        self.env
+            .stdin()
+            .read_to_end(&mut bytes)
+            .await
+            .context("failed to read the standard input")?;
+        let source = String::from_utf8_lossy(&bytes).into_owned();
+
+        let p = parse(hir, &source);
+
+        let mut source_files = SimpleFiles::new();
+        let file_id = source_files.add(STDIN_NAME.to_string(), source.clone());
+        let mut diagnostics = parse_error_diagnostics(file_id, &p.errors);
+
+        let mut result = Ok(());
+
+        if !p.errors.is_empty() && !cmd.force {
+            result = Err(anyhow!("the source has syntax errors"));
+        } else {
+            let formatted = format_syntax(p.into_syntax(), format_opts);
+
+            if cmd.diff || cmd.check {
+                if source != formatted {
+                    if cmd.diff {
+                        let mut stdout = self.env.stdout();
+                        stdout
+                            .write_all(unified_diff(STDIN_NAME, &source, &formatted).as_bytes())
+                            .await?;
+                        stdout.flush().await?;
+                    } else if cmd.output_format == OutputFormat::Human {
+                        tracing::error!("the source is not properly formatted");
+                    } else {
+                        diagnostics.push(unformatted_diagnostic(file_id));
+                    }
+
+                    result = Err(anyhow!("the source is not properly formatted"));
+                }
+            } else {
+                let mut stdout = self.env.stdout();
+                stdout.write_all(formatted.as_bytes()).await?;
+                stdout.flush().await?;
+            }
+        }
+
+        self.print_diagnostics(&source_files, &diagnostics, cmd.output_format)
+            .await?;
+
+        result
+    }
+}
+
+fn unformatted_diagnostic(file_id: usize) -> Diagnostic<usize> {
+    Diagnostic::error()
+        .with_code("unformatted")
+        .with_message("the file is not properly formatted")
+        .with_labels(Vec::from([Label::primary(file_id, 0..0)]))
+}
+
+fn parse(hir: &Hir, source: &str) -> Parse {
+    let parser = rhai_rowan::Parser::new(source).with_operators(hir.parser_operators());
+
+    if rhai_rowan::util::is_rhai_def(source) {
+        parser.parse_def()
+    } else {
+        parser.parse_script()
+    }
+}
+
+fn unified_diff(name: &str, source: &str, formatted: &str) -> String {
+    TextDiff::from_lines(source, formatted)
+        .unified_diff()
+        .header(name, name)
+        .to_string()
 }
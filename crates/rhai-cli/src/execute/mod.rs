@@ -4,13 +4,14 @@ use crate::{
 };
 use rhai_common::environment::Environment;
 
+mod check;
 mod config;
 mod fmt;
 mod lsp;
 
 impl<E: Environment> Rhai<E> {
     pub async fn execute(&mut self, args: RhaiArgs) -> Result<(), anyhow::Error> {
-        if let RootCommand::Fmt(_) = &args.cmd {
+        if let RootCommand::Fmt(_) | RootCommand::Check(_) = &args.cmd {
             self.load_config(&args).await?
         }
 
@@ -24,6 +25,7 @@ impl<E: Environment> Rhai<E> {
             RootCommand::Lsp { cmd } => self.execute_lsp(cmd).await,
             RootCommand::Config { cmd } => self.execute_config(cmd).await,
             RootCommand::Fmt(cmd) => self.execute_fmt(cmd).await,
+            RootCommand::Check(cmd) => self.execute_check(cmd).await,
         }
     }
 }
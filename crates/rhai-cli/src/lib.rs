@@ -1,5 +1,6 @@
 pub mod args;
 mod execute;
+mod output;
 
 use std::{
     ops::Range,
@@ -7,10 +8,10 @@ use std::{
 };
 
 use anyhow::{anyhow, Context};
-use args::RhaiArgs;
+use args::{OutputFormat, RhaiArgs};
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
-    files::SimpleFile,
+    files::SimpleFiles,
     term::{
         self,
         termcolor::{Ansi, NoColor},
@@ -20,7 +21,7 @@ use figment::{providers::Serialized, Figment};
 use itertools::Itertools;
 use rhai_common::{config::Config, environment::Environment, util::Normalize};
 use rhai_hir::Hir;
-use rhai_rowan::{TextRange, util::is_rhai_def, Parser};
+use rhai_rowan::{util::is_rhai_def, Parser, TextRange};
 use tokio::io::AsyncWriteExt;
 use url::Url;
 
@@ -135,34 +136,49 @@ impl<E: Environment> Rhai<E> {
         Ok(files)
     }
 
-    pub async fn print_parse_errors(
+    /// Print the diagnostics in the given format,
+    /// human-readable output is written to stderr,
+    /// everything else is written to stdout.
+    pub async fn print_diagnostics(
         &self,
-        file: &SimpleFile<&str, &str>,
-        errors: &[rhai_rowan::parser::ParseError],
+        files: &SimpleFiles<String, String>,
+        diagnostics: &[Diagnostic<usize>],
+        format: OutputFormat,
     ) -> Result<(), anyhow::Error> {
-        let mut out_diag = Vec::<u8>::new();
+        let mut out = Vec::<u8>::new();
+
+        match format {
+            OutputFormat::Human => {
+                let config = codespan_reporting::term::Config::default();
+
+                for diag in diagnostics {
+                    if self.colors {
+                        term::emit(&mut Ansi::new(&mut out), &config, files, diag)?;
+                    } else {
+                        term::emit(&mut NoColor::new(&mut out), &config, files, diag)?;
+                    }
+                }
 
-        let config = codespan_reporting::term::Config::default();
+                let mut stderr = self.env.stderr();
+                stderr.write_all(&out).await?;
+                stderr.flush().await?;
+            }
+            OutputFormat::Json | OutputFormat::Sarif => {
+                let report = if format == OutputFormat::Json {
+                    output::json_report(files, diagnostics)?
+                } else {
+                    output::sarif_report(files, diagnostics)?
+                };
 
-        for error in errors.iter().unique_by(|e| e.range) {
-            let diag = Diagnostic::error()
-                .with_message("syntax error")
-                .with_labels(Vec::from([
-                    Label::primary((), std_range(error.range)).with_message(error.to_string())
-                ]));
+                serde_json::to_writer_pretty(&mut out, &report)?;
+                out.push(b'\n');
 
-            if self.colors {
-                term::emit(&mut Ansi::new(&mut out_diag), &config, file, &diag)?;
-            } else {
-                term::emit(&mut NoColor::new(&mut out_diag), &config, file, &diag)?;
+                let mut stdout = self.env.stdout();
+                stdout.write_all(&out).await?;
+                stdout.flush().await?;
             }
         }
 
-        let mut stderr = self.env.stderr();
-
-        stderr.write_all(&out_diag).await?;
-        stderr.flush().await?;
-
         Ok(())
     }
 
@@ -173,6 +189,7 @@ impl<E: Environment> Rhai<E> {
             &self.env,
             &self.env.cwd().context("invalid working directory")?,
         )?;
+        hir.set_import_resolver(self.config.modules.module_resolver());
         for file in self.collect_files(cwd, &self.config, false).await? {
             let bytes = match self.env.read_file(&file).await {
                 Ok(f) => f,
@@ -200,6 +217,29 @@ impl<E: Environment> Rhai<E> {
     }
 }
 
+fn parse_error_diagnostics(
+    file_id: usize,
+    errors: &[rhai_rowan::parser::ParseError],
+) -> Vec<Diagnostic<usize>> {
+    errors
+        .iter()
+        .unique_by(|e| e.range)
+        .map(|error| parse_error_diagnostic(file_id, error))
+        .collect()
+}
+
+fn parse_error_diagnostic(
+    file_id: usize,
+    error: &rhai_rowan::parser::ParseError,
+) -> Diagnostic<usize> {
+    Diagnostic::error()
+        .with_code(error.kind.code())
+        .with_message("syntax error")
+        .with_labels(Vec::from([
+            Label::primary(file_id, std_range(error.range)).with_message(error.kind.to_string())
+        ]))
+}
+
 fn std_range(range: TextRange) -> Range<usize> {
     let start: usize = u32::from(range.start()) as _;
     let end: usize = u32::from(range.end()) as _;
@@ -0,0 +1,198 @@
+//! Machine-readable diagnostic reports.
+
+use std::ops::Range;
+
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label, LabelStyle, Severity},
+    files::{Files, SimpleFiles},
+};
+use itertools::Itertools;
+use serde_json::{json, Value};
+use url::Url;
+
+type SourceFiles = SimpleFiles<String, String>;
+
+/// A report with a list of diagnostics,
+/// positions are both byte offsets and 1-based lines and columns
+/// with columns counted in unicode code points.
+pub(crate) fn json_report(
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic<usize>],
+) -> Result<Value, anyhow::Error> {
+    let diagnostics = diagnostics
+        .iter()
+        .map(|diag| {
+            let primary = primary_label(diag);
+
+            Ok(json!({
+                "code": diag.code,
+                "severity": severity_name(diag.severity),
+                "message": message(diag),
+                "location": primary.map(|label| json_location(files, label)).transpose()?,
+                "related": secondary_labels(diag)
+                    .map(|label| {
+                        Ok(json!({
+                            "message": label.message,
+                            "location": json_location(files, label)?,
+                        }))
+                    })
+                    .collect::<Result<Vec<_>, anyhow::Error>>()?,
+            }))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    Ok(json!({ "diagnostics": diagnostics }))
+}
+
+/// A SARIF 2.1.0 log with a single run.
+pub(crate) fn sarif_report(
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic<usize>],
+) -> Result<Value, anyhow::Error> {
+    let rules = diagnostics
+        .iter()
+        .filter_map(|diag| diag.code.as_deref())
+        .unique()
+        .sorted()
+        .map(|code| json!({ "id": code }))
+        .collect::<Vec<_>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|diag| {
+            let locations = primary_label(diag)
+                .map(|label| {
+                    Ok(json!({
+                        "physicalLocation": sarif_physical_location(files, label)?,
+                    }))
+                })
+                .into_iter()
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            let related_locations = secondary_labels(diag)
+                .enumerate()
+                .map(|(id, label)| {
+                    Ok(json!({
+                        "id": id,
+                        "message": { "text": label.message },
+                        "physicalLocation": sarif_physical_location(files, label)?,
+                    }))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            Ok(json!({
+                "ruleId": diag.code,
+                "level": sarif_level(diag.severity),
+                "message": { "text": message(diag) },
+                "locations": locations,
+                "relatedLocations": related_locations,
+            }))
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    Ok(json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "rhai",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }]
+    }))
+}
+
+fn primary_label(diag: &Diagnostic<usize>) -> Option<&Label<usize>> {
+    diag.labels
+        .iter()
+        .find(|label| label.style == LabelStyle::Primary)
+}
+
+fn secondary_labels(diag: &Diagnostic<usize>) -> impl Iterator<Item = &Label<usize>> {
+    diag.labels
+        .iter()
+        .filter(|label| label.style == LabelStyle::Secondary)
+}
+
+/// The primary label usually has the more specific message,
+/// e.g. for syntax errors.
+fn message(diag: &Diagnostic<usize>) -> &str {
+    match primary_label(diag) {
+        Some(label) if !label.message.is_empty() => &label.message,
+        _ => &diag.message,
+    }
+}
+
+fn json_location(files: &SourceFiles, label: &Label<usize>) -> Result<Value, anyhow::Error> {
+    let (start, end) = line_columns(files, label.file_id, &label.range)?;
+
+    Ok(json!({
+        "file": file_url(files, label.file_id)?,
+        "range": { "start": label.range.start, "end": label.range.end },
+        "start": { "line": start.0, "column": start.1 },
+        "end": { "line": end.0, "column": end.1 },
+    }))
+}
+
+fn sarif_physical_location(
+    files: &SourceFiles,
+    label: &Label<usize>,
+) -> Result<Value, anyhow::Error> {
+    let (start, end) = line_columns(files, label.file_id, &label.range)?;
+
+    Ok(json!({
+        "artifactLocation": { "uri": file_url(files, label.file_id)? },
+        "region": {
+            "startLine": start.0,
+            "startColumn": start.1,
+            "endLine": end.0,
+            "endColumn": end.1,
+            "byteOffset": label.range.start,
+            "byteLength": label.range.len(),
+        }
+    }))
+}
+
+type LineColumn = (usize, usize);
+
+fn line_columns(
+    files: &SourceFiles,
+    file_id: usize,
+    range: &Range<usize>,
+) -> Result<(LineColumn, LineColumn), anyhow::Error> {
+    let start = files.location(file_id, range.start)?;
+    let end = files.location(file_id, range.end)?;
+
+    Ok((
+        (start.line_number, start.column_number),
+        (end.line_number, end.column_number),
+    ))
+}
+
+fn file_url(files: &SourceFiles, file_id: usize) -> Result<String, anyhow::Error> {
+    let name = files.name(file_id)?;
+
+    Ok(Url::parse(&format!("file://{name}")).map_or(name, String::from))
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
@@ -0,0 +1,194 @@
+use clap::Parser;
+use common::{json_codes, TestEnvironment};
+use rhai_cli::{args::RhaiArgs, Rhai};
+
+mod common;
+
+const REPORTED: &str = "let a = 1;\nlet x = 1;\nx + y\n";
+
+async fn check(env: &TestEnvironment, args: &[&str]) -> Result<(), anyhow::Error> {
+    let args = RhaiArgs::parse_from(["rhai", "check"].into_iter().chain(args.iter().copied()));
+    Rhai::new(env.clone()).execute(args).await
+}
+
+fn json_severities(report: &str) -> Vec<String> {
+    let report: serde_json::Value = serde_json::from_str(report).unwrap();
+
+    report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diag| diag["severity"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// The item of the array with the given string field.
+fn find<'a>(items: &'a serde_json::Value, field: &str, value: &str) -> &'a serde_json::Value {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item[field] == value)
+        .unwrap_or_else(|| panic!("no item with {field} = {value}"))
+}
+
+#[tokio::test]
+async fn test_errors_fail() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("script.rhai"), "let x = y;\nx\n").unwrap();
+
+    assert!(check(&env, &["--output-format", "json"]).await.is_err());
+
+    assert_eq!(json_codes(&env.stdout.text()), ["unresolved-reference"]);
+}
+
+#[tokio::test]
+async fn test_warnings_pass() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("script.rhai"), "let x = 1;\n").unwrap();
+
+    check(&env, &["--output-format", "json"]).await.unwrap();
+
+    let report = env.stdout.text();
+    assert_eq!(json_codes(&report), ["unused-variable"]);
+    assert_eq!(json_severities(&report), ["warning"]);
+}
+
+#[tokio::test]
+async fn test_syntax_errors_skip_semantic_errors() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("broken.rhai"), "let x = ;\nlet y = z;\n").unwrap();
+    std::fs::write(env.path("script.rhai"), "let x = 1;\n").unwrap();
+
+    assert!(check(&env, &["--output-format", "json"]).await.is_err());
+
+    let mut codes = json_codes(&env.stdout.text());
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes, ["unexpected-token", "unused-variable"]);
+}
+
+#[tokio::test]
+async fn test_lint_levels() {
+    let env = TestEnvironment::new("");
+    let config = env.path("Rhai.toml");
+    std::fs::write(
+        &config,
+        "[lint]\nunresolved-reference = \"allow\"\nunused-variable = \"error\"\n",
+    )
+    .unwrap();
+    std::fs::write(env.path("script.rhai"), "let x = y;\nlet z = 1;\n").unwrap();
+
+    assert!(check(
+        &env,
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "--output-format",
+            "json"
+        ]
+    )
+    .await
+    .is_err());
+
+    let report = env.stdout.text();
+    assert_eq!(json_codes(&report), ["unused-variable", "unused-variable"]);
+    assert_eq!(json_severities(&report), ["error", "error"]);
+}
+
+#[tokio::test]
+async fn test_allowed_lints_pass() {
+    let env = TestEnvironment::new("");
+    let config = env.path("Rhai.toml");
+    std::fs::write(&config, "[lint]\nunused-variable = \"allow\"\n").unwrap();
+    std::fs::write(env.path("script.rhai"), "let x = 1;\n").unwrap();
+
+    check(
+        &env,
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "--output-format",
+            "json",
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert!(json_codes(&env.stdout.text()).is_empty());
+}
+
+#[tokio::test]
+async fn test_json_report() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("script.rhai"), REPORTED).unwrap();
+
+    assert!(check(&env, &["--output-format", "json"]).await.is_err());
+
+    let report: serde_json::Value = serde_json::from_str(&env.stdout.text()).unwrap();
+    let diagnostics = &report["diagnostics"];
+    assert_eq!(diagnostics.as_array().unwrap().len(), 2);
+
+    let unused = find(diagnostics, "code", "unused-variable");
+    assert_eq!(unused["severity"], "warning");
+    assert_eq!(unused["location"]["range"]["start"], 4);
+    assert_eq!(unused["location"]["range"]["end"], 5);
+    assert_eq!(unused["location"]["start"]["line"], 1);
+    assert_eq!(unused["location"]["start"]["column"], 5);
+
+    let unresolved = find(diagnostics, "code", "unresolved-reference");
+    assert_eq!(unresolved["severity"], "error");
+    assert_eq!(unresolved["location"]["start"]["line"], 3);
+    assert_eq!(unresolved["location"]["start"]["column"], 5);
+    assert_eq!(unresolved["location"]["end"]["line"], 3);
+    assert_eq!(unresolved["location"]["end"]["column"], 6);
+    assert!(unresolved["location"]["file"]
+        .as_str()
+        .unwrap()
+        .ends_with("/script.rhai"));
+}
+
+#[tokio::test]
+async fn test_sarif_report() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("script.rhai"), REPORTED).unwrap();
+
+    assert!(check(&env, &["--output-format", "sarif"]).await.is_err());
+
+    let report: serde_json::Value = serde_json::from_str(&env.stdout.text()).unwrap();
+    assert_eq!(
+        report["$schema"],
+        "https://json.schemastore.org/sarif-2.1.0.json"
+    );
+    assert_eq!(report["version"], "2.1.0");
+
+    let run = &report["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rhai");
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        serde_json::json!([{ "id": "unresolved-reference" }, { "id": "unused-variable" }])
+    );
+
+    let results = &run["results"];
+    assert_eq!(results.as_array().unwrap().len(), 2);
+
+    let unused = find(results, "ruleId", "unused-variable");
+    assert_eq!(unused["level"], "warning");
+
+    let unresolved = find(results, "ruleId", "unresolved-reference");
+    assert_eq!(unresolved["level"], "error");
+
+    let location = &unresolved["locations"][0]["physicalLocation"];
+    assert!(location["artifactLocation"]["uri"]
+        .as_str()
+        .unwrap()
+        .ends_with("/script.rhai"));
+
+    let region = &location["region"];
+    assert_eq!(region["startLine"], 3);
+    assert_eq!(region["startColumn"], 5);
+    assert_eq!(region["endLine"], 3);
+    assert_eq!(region["endColumn"], 6);
+    assert_eq!(region["byteOffset"], 26);
+    assert_eq!(region["byteLength"], 1);
+}
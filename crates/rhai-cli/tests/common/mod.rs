@@ -0,0 +1,160 @@
+use std::{
+    io::{self, Cursor},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use rhai_common::environment::{native::NativeEnvironment, Environment};
+use tokio::io::AsyncWrite;
+use url::Url;
+
+/// Captured output of the standard output or error.
+#[derive(Clone, Default)]
+pub struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl Output {
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl AsyncWrite for Output {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A native environment in a temporary directory
+/// with in-memory standard streams.
+#[derive(Clone)]
+pub struct TestEnvironment {
+    cwd: Arc<tempfile::TempDir>,
+    stdin: Arc<Vec<u8>>,
+    pub stdout: Output,
+    pub stderr: Output,
+}
+
+impl TestEnvironment {
+    pub fn new(stdin: &str) -> Self {
+        Self {
+            cwd: Arc::new(tempfile::tempdir().unwrap()),
+            stdin: Arc::new(stdin.as_bytes().to_vec()),
+            stdout: Output::default(),
+            stderr: Output::default(),
+        }
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.cwd.path().join(name)
+    }
+}
+
+#[async_trait(?Send)]
+impl Environment for TestEnvironment {
+    type Stdin = Cursor<Vec<u8>>;
+    type Stdout = Output;
+    type Stderr = Output;
+
+    fn atty_stderr(&self) -> bool {
+        false
+    }
+
+    fn stdin(&self) -> Self::Stdin {
+        Cursor::new(self.stdin.to_vec())
+    }
+
+    fn stdout(&self) -> Self::Stdout {
+        self.stdout.clone()
+    }
+
+    fn stderr(&self) -> Self::Stderr {
+        self.stderr.clone()
+    }
+
+    fn spawn<F>(&self, fut: F)
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send,
+    {
+        NativeEnvironment.spawn(fut);
+    }
+
+    fn spawn_local<F>(&self, fut: F)
+    where
+        F: std::future::Future + 'static,
+    {
+        NativeEnvironment.spawn_local(fut);
+    }
+
+    fn env_var(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>, anyhow::Error> {
+        NativeEnvironment.read_file(path).await
+    }
+
+    async fn write_file(&self, path: &Path, bytes: &[u8]) -> Result<(), anyhow::Error> {
+        NativeEnvironment.write_file(path, bytes).await
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<(), anyhow::Error> {
+        NativeEnvironment.create_dir_all(path).await
+    }
+
+    fn url_to_file_path(&self, url: &Url) -> Option<PathBuf> {
+        NativeEnvironment.url_to_file_path(url)
+    }
+
+    fn cwd(&self) -> Option<PathBuf> {
+        Some(self.cwd.path().to_path_buf())
+    }
+
+    fn glob_files(&self, glob: &str) -> Result<Vec<PathBuf>, anyhow::Error> {
+        NativeEnvironment.glob_files(glob)
+    }
+
+    fn is_absolute(&self, path: &Path) -> bool {
+        NativeEnvironment.is_absolute(path)
+    }
+
+    fn discover_rhai_config(&self, _root: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn is_dir(&self, root: &Path) -> bool {
+        NativeEnvironment.is_dir(root)
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        NativeEnvironment.sleep(duration).await;
+    }
+}
+
+pub fn json_codes(report: &str) -> Vec<String> {
+    let report: serde_json::Value = serde_json::from_str(report).unwrap();
+
+    report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diag| diag["code"].as_str().unwrap().to_string())
+        .collect()
+}
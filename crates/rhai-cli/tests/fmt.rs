@@ -0,0 +1,114 @@
+use clap::Parser;
+use common::{json_codes, TestEnvironment};
+use rhai_cli::{args::FmtCommand, Rhai};
+
+mod common;
+
+const UNFORMATTED: &str = "let x=1\n";
+const FORMATTED: &str = "let x = 1\n";
+
+async fn fmt(env: &TestEnvironment, args: &[&str]) -> Result<(), anyhow::Error> {
+    let cmd = FmtCommand::parse_from(std::iter::once("fmt").chain(args.iter().copied()));
+    Rhai::new(env.clone()).execute_fmt(cmd).await
+}
+
+#[tokio::test]
+async fn test_stdin() {
+    let env = TestEnvironment::new(UNFORMATTED);
+
+    fmt(&env, &["-"]).await.unwrap();
+
+    assert_eq!(env.stdout.text(), FORMATTED);
+}
+
+#[tokio::test]
+async fn test_stdin_diff() {
+    let env = TestEnvironment::new(UNFORMATTED);
+
+    assert!(fmt(&env, &["--diff", "-"]).await.is_err());
+
+    let diff = env.stdout.text();
+    assert!(diff.starts_with("--- <stdin>\n+++ <stdin>\n"), "{diff}");
+    assert!(diff.contains("-let x=1\n+let x = 1\n"), "{diff}");
+}
+
+#[tokio::test]
+async fn test_stdin_formatted_diff() {
+    let env = TestEnvironment::new(FORMATTED);
+
+    fmt(&env, &["--diff", "-"]).await.unwrap();
+
+    assert_eq!(env.stdout.text(), "");
+}
+
+#[tokio::test]
+async fn test_stdin_check_json() {
+    let env = TestEnvironment::new(UNFORMATTED);
+
+    assert!(fmt(&env, &["--check", "--output-format", "json", "-"])
+        .await
+        .is_err());
+
+    assert_eq!(json_codes(&env.stdout.text()), ["unformatted"]);
+}
+
+#[tokio::test]
+async fn test_stdin_syntax_errors_json() {
+    let env = TestEnvironment::new("let x = ;\n");
+
+    assert!(fmt(
+        &env,
+        &["--check", "--force", "--output-format", "json", "-"]
+    )
+    .await
+    .is_err());
+
+    let codes = json_codes(&env.stdout.text());
+    assert!(
+        codes.iter().any(|code| code == "unexpected-token"),
+        "{codes:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_reports_are_not_mixed_with_sources() {
+    let env = TestEnvironment::new(UNFORMATTED);
+
+    assert!(fmt(&env, &["--diff", "--output-format", "json", "-"])
+        .await
+        .is_err());
+    assert!(fmt(&env, &["--force", "--output-format", "sarif", "-"])
+        .await
+        .is_err());
+    assert!(fmt(&env, &["--diff", "--output-format", "json"])
+        .await
+        .is_err());
+
+    assert_eq!(env.stdout.text(), "");
+}
+
+#[tokio::test]
+async fn test_file_diff() {
+    let env = TestEnvironment::new("");
+    let path = env.path("script.rhai");
+    std::fs::write(&path, UNFORMATTED).unwrap();
+
+    assert!(fmt(&env, &["--diff"]).await.is_err());
+
+    let diff = env.stdout.text();
+    assert!(diff.contains("-let x=1\n+let x = 1\n"), "{diff}");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), UNFORMATTED);
+}
+
+#[tokio::test]
+async fn test_file_check_json() {
+    let env = TestEnvironment::new("");
+    std::fs::write(env.path("script.rhai"), UNFORMATTED).unwrap();
+    std::fs::write(env.path("formatted.rhai"), FORMATTED).unwrap();
+
+    assert!(fmt(&env, &["--check", "--output-format", "json"])
+        .await
+        .is_err());
+
+    assert_eq!(json_codes(&env.stdout.text()), ["unformatted"]);
+}
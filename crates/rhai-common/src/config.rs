@@ -2,9 +2,12 @@ use crate::{
     environment::Environment,
     util::{GlobRule, Normalize},
 };
+use rhai_hir::{error::ErrorKind, module::ConfiguredModuleResolver};
+use rhai_rowan::parser::ParseErrorKind;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
+use url::Url;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
@@ -12,11 +15,18 @@ pub struct Config {
     pub source: SourceConfig,
     #[serde(default)]
     pub fmt: FmtConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub modules: ModulesConfig,
 }
 
 impl Config {
     pub fn prepare(&mut self, e: &impl Environment, base: &Path) -> anyhow::Result<()> {
-        self.source.prepare(e, base)
+        self.source.prepare(e, base)?;
+        self.modules.prepare(e, base);
+        self.lint.prepare();
+        Ok(())
     }
 }
 
@@ -92,3 +102,226 @@ pub struct FmtConfig {
     #[serde(default)]
     pub options: rhai_fmt::options::OptionsIncomplete,
 }
+
+/// Resolution of imported modules, this should match
+/// the module resolvers of the host engine.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModulesConfig {
+    /// Directories that are searched for imported modules,
+    /// like the base path of a file module resolver.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// The extension of module files, `rhai` by default.
+    pub extension: Option<String>,
+    /// Modules that are imported by name, e.g. with a static module resolver,
+    /// mapped to definition file paths or URLs.
+    ///
+    /// e.g. `crypto = "defs/crypto.d.rhai"`
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+}
+
+impl ModulesConfig {
+    pub fn prepare(&mut self, e: &impl Environment, base: &Path) {
+        let make_absolute = |path: &mut String| {
+            if !e.is_absolute(Path::new(path.as_str())) {
+                *path = base
+                    .join(path.as_str())
+                    .normalize()
+                    .to_string_lossy()
+                    .into_owned();
+            }
+        };
+
+        self.paths.iter_mut().for_each(make_absolute);
+        self.names
+            .values_mut()
+            .filter(|target| !is_url(target))
+            .for_each(make_absolute);
+    }
+
+    /// A module resolver for the configuration,
+    /// the configuration must be prepared first.
+    #[must_use]
+    pub fn module_resolver(&self) -> ConfiguredModuleResolver {
+        let mut resolver = ConfiguredModuleResolver::new();
+
+        for path in &self.paths {
+            match Url::parse(&format!("file://{}/", path.trim_end_matches('/'))) {
+                Ok(url) => resolver = resolver.with_base_url(url.normalize()),
+                Err(error) => tracing::warn!(%error, %path, "invalid module path"),
+            }
+        }
+
+        if let Some(extension) = &self.extension {
+            resolver = resolver.with_extension(extension.trim_start_matches('.'));
+        }
+
+        for (name, target) in &self.names {
+            let url = if is_url(target) {
+                Url::parse(target)
+            } else {
+                Url::parse(&format!("file://{target}"))
+            };
+
+            match url {
+                Ok(url) => resolver = resolver.with_named_module(name, url.normalize()),
+                Err(error) => tracing::warn!(%error, %name, "invalid module URL"),
+            }
+        }
+
+        resolver
+    }
+}
+
+/// Windows paths with drive letters are not URLs.
+fn is_url(s: &str) -> bool {
+    Url::parse(s).is_ok_and(|url| url.scheme().len() > 1)
+}
+
+/// Lint levels by diagnostic code,
+/// e.g. `unused-variable = "allow"`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct LintConfig {
+    pub levels: BTreeMap<String, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn prepare(&self) {
+        for code in self.unknown_codes() {
+            tracing::warn!(%code, "unknown lint code");
+        }
+    }
+
+    /// The configured level for the code or the given default.
+    #[must_use]
+    pub fn level(&self, code: &str, default: LintLevel) -> LintLevel {
+        self.levels.get(code).copied().unwrap_or(default)
+    }
+
+    /// The configured level for the error.
+    ///
+    /// Unused code is reported as a warning and
+    /// everything else as an error by default.
+    #[must_use]
+    pub fn level_for(&self, kind: &ErrorKind) -> LintLevel {
+        let default = if kind.is_unused() {
+            LintLevel::Warn
+        } else {
+            LintLevel::Error
+        };
+
+        self.level(kind.code(), default)
+    }
+
+    /// The configured level for the syntax error.
+    ///
+    /// Syntax errors are reported as errors by default.
+    #[must_use]
+    pub fn level_for_syntax_error(&self, kind: &ParseErrorKind) -> LintLevel {
+        self.level(kind.code(), LintLevel::Error)
+    }
+
+    /// Configured codes that no error has.
+    pub fn unknown_codes(&self) -> impl Iterator<Item = &str> {
+        self.levels.keys().map(String::as_str).filter(|code| {
+            !ErrorKind::CODES.contains(code) && !ParseErrorKind::CODES.contains(code)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Do not report the diagnostic.
+    Allow,
+    /// Report the diagnostic as a warning.
+    Warn,
+    /// Report the diagnostic as an error.
+    Error,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_hir::Symbol;
+    use rhai_rowan::TextRange;
+
+    fn lint_config(json: &str) -> LintConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_default_levels() {
+        let lint = LintConfig::default();
+
+        let unused = ErrorKind::UnusedSuppression {
+            range: TextRange::default(),
+            code: "unused-variable".into(),
+        };
+        let unresolved = ErrorKind::UnresolvedImport {
+            import: Symbol::default(),
+        };
+
+        assert_eq!(lint.level_for(&unused), LintLevel::Warn);
+        assert_eq!(lint.level_for(&unresolved), LintLevel::Error);
+    }
+
+    #[test]
+    fn test_configured_levels() {
+        let lint =
+            lint_config(r#"{ "unused-suppression": "error", "unresolved-import": "allow" }"#);
+
+        let unused = ErrorKind::UnusedSuppression {
+            range: TextRange::default(),
+            code: "unused-variable".into(),
+        };
+        let unresolved = ErrorKind::UnresolvedImport {
+            import: Symbol::default(),
+        };
+
+        assert_eq!(lint.level_for(&unused), LintLevel::Error);
+        assert_eq!(lint.level_for(&unresolved), LintLevel::Allow);
+        assert_eq!(
+            lint.level("nested-function", LintLevel::Warn),
+            LintLevel::Warn
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_levels() {
+        let lint = lint_config(r#"{ "unexpected-token": "allow" }"#);
+
+        assert_eq!(
+            lint.level_for_syntax_error(&ParseErrorKind::UnexpectedToken),
+            LintLevel::Allow
+        );
+        assert_eq!(
+            LintConfig::default().level_for_syntax_error(&ParseErrorKind::UnexpectedToken),
+            LintLevel::Error
+        );
+    }
+
+    #[test]
+    fn test_unknown_codes() {
+        let lint = lint_config(
+            r#"{
+                "unused-variable": "allow",
+                "unexpected-token": "warn",
+                "unused-variables": "allow",
+                "not-a-lint": "error"
+            }"#,
+        );
+
+        assert_eq!(
+            lint.unknown_codes().collect::<Vec<_>>(),
+            ["not-a-lint", "unused-variables"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_level() {
+        assert!(serde_json::from_str::<LintConfig>(r#"{ "unused-variable": "deny" }"#).is_err());
+    }
+}
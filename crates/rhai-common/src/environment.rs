@@ -36,6 +36,9 @@ pub trait Environment: Clone + Send + Sync + 'static {
 
     async fn write_file(&self, path: &Path, bytes: &[u8]) -> Result<(), anyhow::Error>;
 
+    /// Create a directory and all of its missing parents.
+    async fn create_dir_all(&self, path: &Path) -> Result<(), anyhow::Error>;
+
     fn url_to_file_path(&self, url: &Url) -> Option<PathBuf>;
 
     /// Absolute current working dir.
@@ -58,6 +58,10 @@ impl Environment for NativeEnvironment {
         Ok(tokio::fs::write(path, bytes).await?)
     }
 
+    async fn create_dir_all(&self, path: &Path) -> Result<(), anyhow::Error> {
+        Ok(tokio::fs::create_dir_all(path).await?)
+    }
+
     fn url_to_file_path(&self, url: &Url) -> Option<std::path::PathBuf> {
         url.to_file_path().ok()
     }
@@ -189,6 +189,28 @@ impl<W: Write> Formatter<W> {
         Ok(())
     }
 
+    /// Add text copied from the source as-is.
+    ///
+    /// Text that spans multiple lines breaks all the enclosing boxes.
+    pub(crate) fn scan_verbatim(&mut self, string: &'static str) -> io::Result<()> {
+        if !string.contains('\n') {
+            return self.scan_string(string);
+        }
+
+        if self.scan_stack.is_empty() {
+            self.print_string(string)?;
+        } else {
+            self.buf.push(BufEntry {
+                token: Token::String(string),
+                size: SIZE_INFINITY,
+            });
+            self.right_total += SIZE_INFINITY;
+            self.check_stream()?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn offset(&mut self, offset: isize) {
         match &mut self.buf.last_mut().token {
             Token::Break(token) => token.offset += offset,
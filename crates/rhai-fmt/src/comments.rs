@@ -18,12 +18,30 @@
 //!
 //! This file also contains utilities for comments in
 //! other positions.
+//!
+//! Formatting can be disabled for statements with comments:
+//!
+//! ```rhai
+//! // rhai-fmt: skip
+//! let table = [
+//!     1, 0,
+//!     0, 1,
+//! ];
+//!
+//! // rhai-fmt: off
+//! let a   = 1;
+//! let bar = 2;
+//! // rhai-fmt: on
+//! ```
+//!
+//! `skip` applies to the next statement only, `off` and `on`
+//! apply to the statements between them in the same block.
 
 #![allow(dead_code)]
 use rhai_rowan::syntax::{
     SyntaxElement,
     SyntaxKind::{self, *},
-    SyntaxNode,
+    SyntaxNode, SyntaxToken,
 };
 use rowan::Direction;
 
@@ -44,6 +62,8 @@ impl<W: Write> Formatter<W> {
 
         let ws_and_comments = node
             .children_with_tokens()
+            // Whitespace before the opening token, e.g. `{`.
+            .skip_while(|e| e.kind() == WHITESPACE)
             .skip_while(|e| {
                 e.as_token().is_some()
                     && !matches!(e.kind(), WHITESPACE | COMMENT_BLOCK | COMMENT_LINE)
@@ -359,3 +379,45 @@ pub(crate) fn comments_in_expr(expr: &SyntaxNode) -> bool {
     expr.children_with_tokens()
         .any(|c| matches!(c.kind(), COMMENT_LINE | COMMENT_BLOCK))
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Off,
+    On,
+    Skip,
+}
+
+fn directive(comment: &SyntaxToken) -> Option<Directive> {
+    let text = match comment.kind() {
+        COMMENT_LINE => comment.text().strip_prefix("//")?,
+        COMMENT_BLOCK => comment.text().strip_prefix("/*")?.strip_suffix("*/")?,
+        _ => return None,
+    };
+
+    match text.trim().strip_prefix("rhai-fmt:")?.trim() {
+        "off" => Some(Directive::Off),
+        "on" => Some(Directive::On),
+        "skip" => Some(Directive::Skip),
+        _ => None,
+    }
+}
+
+/// Whether a statement should be left as-is
+/// because of a directive comment before it.
+pub(crate) fn formatting_disabled(stmt: &SyntaxNode) -> bool {
+    let mut directly_before = true;
+
+    for element in stmt.siblings_with_tokens(Direction::Prev).skip(1) {
+        match element {
+            SyntaxElement::Node(_) => directly_before = false,
+            SyntaxElement::Token(token) => match directive(&token) {
+                Some(Directive::Skip) if directly_before => return true,
+                Some(Directive::Off) => return true,
+                Some(Directive::On) => return false,
+                _ => {}
+            },
+        }
+    }
+
+    false
+}
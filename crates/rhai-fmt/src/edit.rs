@@ -0,0 +1,87 @@
+//! Minimal edits between the original and the formatted source.
+//!
+//! Instead of replacing the entire source, the token streams
+//! of the original and the formatted source are diffed,
+//! so that only the changed tokens (mostly whitespace) are replaced.
+
+use rhai_rowan::{
+    parser::Parser,
+    syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken},
+    TextRange,
+};
+use similar::{Algorithm, DiffTag};
+
+use crate::{format_syntax, Options};
+
+/// A replacement of a range in the original source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+/// Format the syntax tree and return the edits
+/// that turn the original source into the formatted one.
+#[must_use]
+pub fn format_edits(cst: &SyntaxNode, options: Options) -> Vec<TextEdit> {
+    let formatted = format_syntax(cst.clone(), options);
+    let formatted_cst = parse(&formatted, cst.kind() == SyntaxKind::RHAI_DEF);
+
+    diff_syntax(cst, &formatted_cst)
+}
+
+/// The edits that turn the source of one syntax tree into the source of another.
+///
+/// The edits are ordered by their ranges and do not overlap.
+#[must_use]
+pub fn diff_syntax(old: &SyntaxNode, new: &SyntaxNode) -> Vec<TextEdit> {
+    let old_tokens = tokens(old);
+    let new_tokens = tokens(new);
+
+    let old_keys = old_tokens.iter().map(token_key).collect::<Vec<_>>();
+    let new_keys = new_tokens.iter().map(token_key).collect::<Vec<_>>();
+
+    similar::capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys)
+        .into_iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .map(|op| {
+            let old_range = op.old_range();
+
+            let start = old_tokens
+                .get(old_range.start)
+                .map_or(old.text_range().end(), |t| t.text_range().start());
+
+            let end = if old_range.is_empty() {
+                start
+            } else {
+                old_tokens[old_range.end - 1].text_range().end()
+            };
+
+            TextEdit {
+                range: TextRange::new(start, end),
+                new_text: new_tokens[op.new_range()]
+                    .iter()
+                    .map(SyntaxToken::text)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn parse(src: &str, is_def: bool) -> SyntaxNode {
+    if is_def {
+        Parser::new(src).parse_def().into_syntax()
+    } else {
+        Parser::new(src).parse_script().into_syntax()
+    }
+}
+
+fn tokens(node: &SyntaxNode) -> Vec<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .collect()
+}
+
+fn token_key(token: &SyntaxToken) -> (SyntaxKind, String) {
+    (token.kind(), token.text().to_string())
+}
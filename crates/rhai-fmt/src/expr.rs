@@ -12,6 +12,7 @@ use rhai_rowan::{
 use crate::{
     algorithm::Formatter,
     comments::{comments_in_expr, CommentInfo},
+    options::OperatorPosition,
     source::needs_stmt_separator,
     util::{break_count, ScopedStatic},
 };
@@ -228,7 +229,7 @@ impl<S: Write> Formatter<S> {
                 if let Some(ident) = param.ident_token() {
                     self.word(ident.static_text())?;
                 }
-                self.trailing_comma(i + 1 == count)?;
+                self.list_comma(&param_list.syntax(), i + 1 == count, 0)?;
             }
         }
         self.word(")")?;
@@ -393,7 +394,7 @@ impl<S: Write> Formatter<S> {
                 if let Some(ident) = param.ident_token() {
                     self.word(ident.static_text())?;
                 }
-                self.trailing_comma(i + 1 == count)?;
+                self.list_comma(&param_list.syntax(), i + 1 == count, 0)?;
             }
         }
         self.word("|")?;
@@ -422,7 +423,7 @@ impl<S: Write> Formatter<S> {
 
             for (i, arg) in args.arguments().enumerate() {
                 self.fmt_expr(arg)?;
-                self.trailing_comma(i + 1 == count)?;
+                self.list_comma(&args.syntax(), i + 1 == count, 0)?;
             }
         }
         self.offset(-1);
@@ -466,11 +467,15 @@ impl<S: Write> Formatter<S> {
             return self.word("}");
         }
 
+        let brace_space = self.options.space_in_object_braces;
+
         if !comments.comment_added {
             if always_break {
                 self.hardbreak();
-            } else {
+            } else if brace_space {
                 self.space();
+            } else {
+                self.zerobreak();
             }
         }
 
@@ -493,7 +498,7 @@ impl<S: Write> Formatter<S> {
             self.end();
 
             let last = i + 1 == count;
-            self.trailing_comma_or_space(last)?;
+            self.list_comma(&obj_syntax, last, usize::from(brace_space))?;
 
             if last {
                 self.add_standalone_comments_after(&field.syntax())?;
@@ -530,7 +535,7 @@ impl<S: Write> Formatter<S> {
         let count = expr.values().count();
         for (i, value) in expr.values().enumerate() {
             self.fmt_expr(value)?;
-            self.trailing_comma(i + 1 == count)?;
+            self.list_comma(&expr.syntax(), i + 1 == count, 0)?;
         }
         self.offset(-1);
         self.end();
@@ -653,7 +658,7 @@ impl<S: Write> Formatter<S> {
 
                     for (i, arg) in args.arguments().enumerate() {
                         self.fmt_expr(arg)?;
-                        self.trailing_comma(i + 1 == count)?;
+                        self.list_comma(&args.syntax(), i + 1 == count, 0)?;
                     }
                 }
                 self.offset(-1);
@@ -878,11 +883,11 @@ impl<S: Write> Formatter<S> {
         }
 
         if let Some(else_if_branch) = expr.else_if_branch() {
-            self.word(" else ")?;
+            self.fmt_else()?;
             self.fmt_expr_if(else_if_branch, true)?;
         }
         if let Some(else_branch) = expr.else_branch() {
-            self.word(" else ")?;
+            self.fmt_else()?;
             self.fmt_expr_block(else_branch, true, true)?;
         }
 
@@ -943,7 +948,7 @@ impl<S: Write> Formatter<S> {
 
                     self.ibox(0);
 
-                    self.fmt_item(item)?;
+                    self.fmt_stmt_item(&stmt_syntax, item)?;
 
                     if had_sep && needs_sep {
                         self.word(";")?;
@@ -988,7 +993,7 @@ impl<S: Write> Formatter<S> {
 
                     self.ibox(0);
 
-                    self.fmt_item(item)?;
+                    self.fmt_stmt_item(&stmt_syntax, item)?;
 
                     let had_sep = stmt
                         .syntax()
@@ -1029,12 +1034,25 @@ impl<S: Write> Formatter<S> {
         Ok(())
     }
 
+    fn fmt_else(&mut self) -> io::Result<()> {
+        if self.options.else_on_new_line {
+            self.hardbreak();
+            self.offset(-1);
+            self.word("else ")
+        } else {
+            self.word(" else ")
+        }
+    }
+
     fn break_before_op(&mut self, kind: SyntaxKind) -> io::Result<()> {
         match kind {
             T![".."] | T!["..="] => {}
             T!["."] | T!["?."] => self.zerobreak(),
             T!["="] => self.nbsp()?,
-            _ => self.space(),
+            _ => match self.options.binary_op_position {
+                OperatorPosition::Start => self.space(),
+                OperatorPosition::End => self.nbsp()?,
+            },
         }
 
         Ok(())
@@ -1043,7 +1061,11 @@ impl<S: Write> Formatter<S> {
     fn break_after_op(&mut self, kind: SyntaxKind) -> io::Result<()> {
         match kind {
             T![".."] | T!["..="] | T!["."] | T!["?."] => {}
-            _ => self.nbsp()?,
+            T!["="] => self.nbsp()?,
+            _ => match self.options.binary_op_position {
+                OperatorPosition::Start => self.nbsp()?,
+                OperatorPosition::End => self.space(),
+            },
         }
 
         Ok(())
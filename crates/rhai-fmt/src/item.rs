@@ -1,8 +1,11 @@
 use std::io::{self, Write};
 
-use rhai_rowan::ast::{AstNode, Item, Stmt};
+use rhai_rowan::{
+    ast::{AstNode, Item, Stmt},
+    syntax::SyntaxNode,
+};
 
-use crate::{algorithm::Formatter, util::ScopedStatic};
+use crate::{algorithm::Formatter, comments::formatting_disabled, util::ScopedStatic};
 
 impl<S: Write> Formatter<S> {
     pub(crate) fn fmt_stmt(&mut self, stmt: Stmt) -> io::Result<()> {
@@ -12,6 +15,16 @@ impl<S: Write> Formatter<S> {
         Ok(())
     }
 
+    /// Format the item of a statement, or copy it as-is
+    /// if formatting is disabled for the statement.
+    pub(crate) fn fmt_stmt_item(&mut self, stmt: &SyntaxNode, item: Item) -> io::Result<()> {
+        if formatting_disabled(stmt) {
+            self.verbatim(&item.syntax())
+        } else {
+            self.fmt_item(item)
+        }
+    }
+
     pub(crate) fn fmt_item(&mut self, item: Item) -> io::Result<()> {
         self.cbox(0);
 
@@ -22,9 +22,11 @@
 mod algorithm;
 mod comments;
 mod cst;
+mod edit;
 mod expr;
 mod item;
 mod path;
+mod range;
 mod ring;
 mod source;
 mod util;
@@ -35,6 +37,8 @@ pub use options::Options;
 use rhai_rowan::{parser::Parser, syntax::SyntaxElement};
 
 pub use algorithm::Formatter;
+pub use edit::{diff_syntax, format_edits, TextEdit};
+pub use range::format_range;
 
 #[must_use]
 #[allow(clippy::missing_panics_doc)]
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
 macro_rules! create_options {
@@ -125,6 +127,18 @@ create_options! {
         ///
         /// This is typically some amount of spaces or a tab character.
         pub indent_string: String,
+        /// Trailing commas in arrays, object maps, arguments
+        /// and parameters that are broken into multiple lines.
+        pub trailing_comma: TrailingComma,
+        /// Whether to put `else` on a new line instead of
+        /// after the closing brace of the previous block.
+        pub else_on_new_line: bool,
+        /// The placement of binary operators when
+        /// an expression is broken into multiple lines.
+        pub binary_op_position: OperatorPosition,
+        /// Whether to add spaces inside the braces of
+        /// object maps that fit on a single line.
+        pub space_in_object_braces: bool,
     }
 }
 
@@ -135,10 +149,94 @@ impl Default for Options {
             max_width: 80,
             max_empty_lines: 2,
             indent_string: String::from("  "),
+            trailing_comma: TrailingComma::Always,
+            else_on_new_line: false,
+            binary_op_position: OperatorPosition::Start,
+            space_in_object_braces: true,
+        }
+    }
+}
+
+/// Trailing commas in lists that are broken into multiple lines.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingComma {
+    /// Always add a trailing comma.
+    Always,
+    /// Never add a trailing comma.
+    Never,
+    /// Only add a trailing comma if the list already had one.
+    Preserve,
+}
+
+impl FromStr for TrailingComma {
+    type Err = InvalidVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            "preserve" => Ok(Self::Preserve),
+            _ => Err(InvalidVariant {
+                value: s.into(),
+                expected: &["always", "never", "preserve"],
+            }),
+        }
+    }
+}
+
+/// The placement of a binary operator when the
+/// expression is broken into multiple lines.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperatorPosition {
+    /// At the start of the next line.
+    Start,
+    /// At the end of the broken line.
+    End,
+}
+
+impl FromStr for OperatorPosition {
+    type Err = InvalidVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Ok(Self::Start),
+            "end" => Ok(Self::End),
+            _ => Err(InvalidVariant {
+                value: s.into(),
+                expected: &["start", "end"],
+            }),
         }
     }
 }
 
+/// A value that is not one of the accepted values of an option.
+#[derive(Debug)]
+pub struct InvalidVariant {
+    value: String,
+    expected: &'static [&'static str],
+}
+
+impl core::fmt::Display for InvalidVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            r#"expected one of {}, found "{}""#,
+            self.expected
+                .iter()
+                .map(|v| format!(r#""{}""#, v))
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.value
+        )
+    }
+}
+
+impl std::error::Error for InvalidVariant {}
+
 #[derive(Debug)]
 pub enum OptionParseError {
     InvalidOption(String),
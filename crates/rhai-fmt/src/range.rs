@@ -0,0 +1,265 @@
+//! Formatting of parts of a syntax tree.
+//!
+//! Only whole statements are formatted, the smallest
+//! sequence of statements that covers a range is formatted
+//! and the rest of the source is left untouched.
+
+use std::io::{self, Write};
+
+use rhai_rowan::{
+    ast::{AstNode, Stmt},
+    syntax::{SyntaxElement, SyntaxKind::*, SyntaxNode, SyntaxToken},
+    util::trimmed_range,
+    TextRange, TextSize, T,
+};
+use rowan::Direction;
+
+use crate::{
+    algorithm::Formatter,
+    comments::formatting_disabled,
+    edit::{diff_syntax, parse, TextEdit},
+    source::needs_stmt_separator,
+    util::break_count,
+    Options,
+};
+
+/// Format the smallest sequence of statements that covers the given range.
+///
+/// Syntax errors are not checked, edits that overlap
+/// with errors should be discarded by the caller.
+///
+/// Nothing is formatted if any of the statements or the statements
+/// enclosing them have formatting disabled by a directive comment.
+#[must_use]
+#[allow(clippy::missing_panics_doc)]
+pub fn format_range(cst: &SyntaxNode, range: TextRange, mut options: Options) -> Vec<TextEdit> {
+    let Some((container, stmts)) = covering_statements(cst, range) else {
+        return Vec::new();
+    };
+
+    if stmts.iter().any(|stmt| {
+        stmt.ancestors()
+            .filter(|node| node.kind() == STMT)
+            .any(|node| formatting_disabled(&node))
+    }) {
+        return Vec::new();
+    }
+
+    let source = cst.to_string();
+
+    let indent = match container.kind() {
+        EXPR_BLOCK => format!(
+            "{}{}",
+            line_indent(&source, container.text_range().start()),
+            options.indent_string
+        ),
+        _ => String::new(),
+    };
+
+    let (first, last) = match (stmts.first(), stmts.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    let Some(start) = trimmed_range(first).map(TextRange::start) else {
+        return Vec::new();
+    };
+    let Some(mut end) = trimmed_range(last).map(TextRange::end) else {
+        return Vec::new();
+    };
+
+    if let Some(comment) = comment_same_line_after(last) {
+        end = end.max(comment.text_range().end());
+    }
+
+    // Only replace the indentation if the statement
+    // is the first thing on its line.
+    let line_start = TextSize::from(
+        source[..usize::from(start)]
+            .rfind('\n')
+            .map_or(0, |idx| idx + 1) as u32,
+    );
+    let (start, indent_first_line) = if source[usize::from(line_start)..usize::from(start)]
+        .trim()
+        .is_empty()
+    {
+        (line_start, true)
+    } else {
+        (start, false)
+    };
+
+    options.max_width = options
+        .max_width
+        .saturating_sub(indent.chars().count() as u64);
+
+    let mut out = Vec::new();
+    let mut formatter = Formatter::new_with_options(&mut out, options);
+    formatter.fmt_stmt_range(&stmts).unwrap();
+    formatter.eof().unwrap();
+
+    // SAFETY: we only print valid UTF-8.
+    let formatted = unsafe { String::from_utf8_unchecked(out) };
+
+    let mut new_text = String::new();
+
+    for (idx, line) in formatted.trim_end().split('\n').enumerate() {
+        if idx > 0 {
+            new_text.push('\n');
+        }
+
+        if !line.trim().is_empty() && (idx > 0 || indent_first_line) {
+            new_text += &indent;
+        }
+
+        new_text += line;
+    }
+
+    let range = TextRange::new(start, end);
+    let is_def = cst.kind() == RHAI_DEF;
+
+    diff_syntax(&parse(&source[range], is_def), &parse(&new_text, is_def))
+        .into_iter()
+        .map(|edit| TextEdit {
+            range: edit.range + range.start(),
+            new_text: edit.new_text,
+        })
+        .collect()
+}
+
+impl<W: Write> Formatter<W> {
+    /// Format consecutive statements of a block or script,
+    /// similar to [`Formatter::fmt_rhai`] but without comments
+    /// after the last statement.
+    fn fmt_stmt_range(&mut self, stmts: &[SyntaxNode]) -> io::Result<()> {
+        self.cbox(0);
+
+        for (idx, stmt_syntax) in stmts.iter().enumerate() {
+            let Some(stmt) = Stmt::cast(stmt_syntax.clone()) else {
+                continue;
+            };
+            let Some(item) = stmt.item() else {
+                continue;
+            };
+            let item_syntax = item.syntax();
+
+            let last = idx + 1 == stmts.len();
+            let last_in_container = !stmt_syntax
+                .siblings(Direction::Next)
+                .skip(1)
+                .any(|s| s.kind() == STMT);
+            let needs_sep = needs_stmt_separator(&item);
+
+            self.ibox(0);
+
+            self.fmt_stmt_item(stmt_syntax, item)?;
+
+            let had_sep = stmt_syntax
+                .children_with_tokens()
+                .any(|c| c.kind() == T![";"]);
+
+            if last_in_container {
+                if had_sep && needs_sep {
+                    self.word(";")?;
+                }
+            } else if needs_sep {
+                self.word(";")?;
+            }
+
+            self.comment_same_line_after(&item_syntax)?;
+            self.comment_same_line_after(stmt_syntax)?;
+
+            self.end();
+
+            if last {
+                break;
+            }
+
+            let standalone_comments = if had_sep {
+                self.standalone_comments_after(stmt_syntax, true)?
+            } else {
+                self.standalone_comments_after(&item_syntax, true)?
+            };
+
+            if !standalone_comments.hardbreak_end {
+                self.hardbreak();
+            }
+        }
+
+        self.end();
+
+        Ok(())
+    }
+}
+
+/// The statement container and the statements in it that cover the range.
+fn covering_statements(
+    cst: &SyntaxNode,
+    range: TextRange,
+) -> Option<(SyntaxNode, Vec<SyntaxNode>)> {
+    let covering = match cst.covering_element(range) {
+        SyntaxElement::Node(node) => node,
+        SyntaxElement::Token(token) => token.parent()?,
+    };
+
+    for node in covering.ancestors() {
+        let container = match node.kind() {
+            STMT => node.parent()?,
+            RHAI | EXPR_BLOCK => node,
+            _ => continue,
+        };
+
+        let stmts = container
+            .children()
+            .filter(|stmt| stmt.kind() == STMT)
+            .filter(|stmt| intersects(stmt.text_range(), range))
+            .collect::<Vec<_>>();
+
+        if !stmts.is_empty() {
+            return Some((container, stmts));
+        }
+    }
+
+    None
+}
+
+fn intersects(stmt_range: TextRange, range: TextRange) -> bool {
+    if range.is_empty() {
+        stmt_range.contains_inclusive(range.start())
+    } else {
+        stmt_range
+            .intersect(range)
+            .is_some_and(|intersection| !intersection.is_empty())
+    }
+}
+
+/// The comment that the formatter keeps on the same line
+/// after the statement.
+fn comment_same_line_after(stmt: &SyntaxNode) -> Option<SyntaxToken> {
+    let item = stmt.children().find(|n| n.kind() == ITEM);
+
+    item.into_iter()
+        .chain([stmt.clone()])
+        .filter_map(|node| {
+            node.siblings_with_tokens(Direction::Next)
+                .skip(1)
+                .skip_while(|e| {
+                    e.as_token().is_some()
+                        && !matches!(e.kind(), WHITESPACE | COMMENT_BLOCK | COMMENT_LINE)
+                })
+                .take_while(|e| matches!(e.kind(), WHITESPACE | COMMENT_BLOCK | COMMENT_LINE))
+                .filter_map(SyntaxElement::into_token)
+                .take_while(|t| t.kind() != WHITESPACE || break_count(t) == 0)
+                .find(|t| matches!(t.kind(), COMMENT_BLOCK | COMMENT_LINE))
+        })
+        .max_by_key(|comment| comment.text_range().end())
+}
+
+fn line_indent(source: &str, offset: TextSize) -> &str {
+    let line_start = source[..usize::from(offset)]
+        .rfind('\n')
+        .map_or(0, |idx| idx + 1);
+
+    let line = &source[line_start..];
+
+    &line[..line.len() - line.trim_start().len()]
+}
@@ -35,7 +35,7 @@ impl<S: Write> Formatter<S> {
 
             self.ibox(0);
 
-            self.fmt_item(item)?;
+            self.fmt_stmt_item(&stmt_syntax, item)?;
 
             let had_sep = stmt
                 .syntax()
@@ -1,8 +1,11 @@
 #![allow(dead_code)]
-use rhai_rowan::syntax::{SyntaxKind::*, SyntaxNode, SyntaxToken};
+use rhai_rowan::syntax::{SyntaxElement, SyntaxKind::*, SyntaxNode, SyntaxToken};
 use rowan::Direction;
 
-use crate::algorithm::{self, BeginToken, BreakToken, Breaks, Formatter};
+use crate::{
+    algorithm::{self, BeginToken, BreakToken, Breaks, Formatter},
+    options::TrailingComma,
+};
 use std::{
     io::{self, Write},
     mem,
@@ -31,6 +34,26 @@ impl<W: Write> Formatter<W> {
         self.scan_string(wrd)
     }
 
+    /// Copy the original text of the node
+    /// without the surrounding whitespace.
+    pub(crate) fn verbatim(&mut self, node: &SyntaxNode) -> io::Result<()> {
+        let tokens = node
+            .descendants_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .collect::<Vec<_>>();
+
+        let start = tokens.iter().position(|t| t.kind() != WHITESPACE);
+        let end = tokens.iter().rposition(|t| t.kind() != WHITESPACE);
+
+        if let (Some(start), Some(end)) = (start, end) {
+            for token in &tokens[start..=end] {
+                self.scan_verbatim(token.static_text())?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn spaces(&mut self, n: usize) {
         self.scan_break(BreakToken {
             blank_space: n,
@@ -90,11 +113,27 @@ impl<W: Write> Formatter<W> {
         Ok(())
     }
 
-    pub(crate) fn trailing_comma_or_space(&mut self, is_last: bool) -> io::Result<()> {
+    /// A comma after an item of the list, the trailing comma
+    /// depends on [`Options::trailing_comma`](crate::Options::trailing_comma).
+    ///
+    /// The blank space is added after the last item
+    /// if the list is not broken.
+    pub(crate) fn list_comma(
+        &mut self,
+        list: &SyntaxNode,
+        is_last: bool,
+        blank_space: usize,
+    ) -> io::Result<()> {
         if is_last {
+            let trailing = match self.options.trailing_comma {
+                TrailingComma::Always => true,
+                TrailingComma::Never => false,
+                TrailingComma::Preserve => has_trailing_comma(list),
+            };
+
             self.scan_break(BreakToken {
-                blank_space: 1,
-                pre_break: Some(','),
+                blank_space,
+                pre_break: trailing.then_some(','),
                 ..BreakToken::default()
             });
         } else {
@@ -143,3 +182,15 @@ pub(crate) fn break_count(t: &SyntaxToken) -> u64 {
     }
     t.text().chars().filter(|c| *c == '\n').count() as u64
 }
+
+/// Whether the last item of the list is followed by a comma.
+pub(crate) fn has_trailing_comma(list: &SyntaxNode) -> bool {
+    list.children_with_tokens()
+        .filter(|c| !matches!(c.kind(), WHITESPACE | COMMENT_BLOCK | COMMENT_LINE))
+        .collect::<Vec<_>>()
+        .iter()
+        .rev()
+        // Skip the closing delimiter.
+        .nth(1)
+        .is_some_and(|c| c.kind() == PUNCT_COMMA)
+}
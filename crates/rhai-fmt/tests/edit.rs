@@ -0,0 +1,35 @@
+use rhai_fmt::{format_edits, format_source, Options};
+use rhai_rowan::parser::Parser;
+use test_case::test_case;
+
+#[test_case("simple", include_str!("../../../testdata/valid/simple.rhai"))]
+#[test_case("comments", include_str!("../../../testdata/valid/comments.rhai"))]
+#[test_case("oop", include_str!("../../../testdata/valid/oop.rhai"))]
+#[test_case("switch", include_str!("../../../testdata/valid/switch.rhai"))]
+#[test_case("template", include_str!("../../../testdata/valid/template.rhai"))]
+fn format_with_edits(_name: &str, src: &str) {
+    let cst = Parser::new(src).parse_script().into_syntax();
+
+    let mut formatted = src.to_string();
+
+    for edit in format_edits(&cst, Options::default()).into_iter().rev() {
+        formatted.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.new_text);
+    }
+
+    assert_eq!(formatted, format_source(src, Options::default()));
+}
+
+#[test]
+fn test_minimal_edits() {
+    let src = "let  a = 1;\nlet b = [1,2];\n";
+    let cst = Parser::new(src).parse_script().into_syntax();
+
+    let edits = format_edits(&cst, Options::default());
+
+    let replaced = edits
+        .iter()
+        .map(|edit| (&src[edit.range], edit.new_text.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(replaced, [("  ", " "), ("", " ")]);
+}
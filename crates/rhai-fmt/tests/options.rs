@@ -0,0 +1,103 @@
+use rhai_fmt::{
+    format_source,
+    options::{OperatorPosition, TrailingComma},
+    Options,
+};
+
+const LONG_ARRAY: &str =
+    "let a = [100000000, 200000000, 300000000, 400000000, 500000000, 600000000, 7000000];\n";
+
+#[test]
+fn test_trailing_comma() {
+    let opts = |trailing_comma| Options {
+        trailing_comma,
+        ..Options::default()
+    };
+
+    assert!(format_source(LONG_ARRAY, opts(TrailingComma::Always)).contains("  7000000,\n];"));
+    assert!(format_source(LONG_ARRAY, opts(TrailingComma::Never)).contains("  7000000\n];"));
+    assert!(format_source(LONG_ARRAY, opts(TrailingComma::Preserve)).contains("  7000000\n];"));
+
+    let src = "foo(aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb, cccccccccccccccccc,);\n";
+
+    assert_eq!(
+        format_source(src, opts(TrailingComma::Preserve)),
+        "foo(\n  aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,\n  bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb,\n  cccccccccccccccccc,\n);\n"
+    );
+
+    // Lists that fit on a line never have a trailing comma.
+    assert_eq!(
+        format_source("let b = [1, 2,];\n", opts(TrailingComma::Always)),
+        "let b = [1, 2];\n"
+    );
+}
+
+#[test]
+fn test_else_on_new_line() {
+    let opts = Options {
+        else_on_new_line: true,
+        ..Options::default()
+    };
+
+    assert_eq!(
+        format_source("if a { 1 } else if b { 2 } else { 3 }\n", opts),
+        "if a {\n  1\n}\nelse if b {\n  2\n}\nelse {\n  3\n}\n"
+    );
+}
+
+#[test]
+fn test_binary_op_position() {
+    let src = "let x = aaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb + cccccccccccccccccccc;\n";
+
+    let opts = |binary_op_position| Options {
+        binary_op_position,
+        ..Options::default()
+    };
+
+    assert_eq!(
+        format_source(src, opts(OperatorPosition::Start)),
+        "let x = aaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n    + cccccccccccccccccccc;\n"
+    );
+    assert_eq!(
+        format_source(src, opts(OperatorPosition::End)),
+        "let x = aaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb +\n    cccccccccccccccccccc;\n"
+    );
+}
+
+#[test]
+fn test_space_in_object_braces() {
+    let src = "let o = #{a: 1, b: 2};\n";
+
+    let opts = |space_in_object_braces| Options {
+        space_in_object_braces,
+        ..Options::default()
+    };
+
+    assert_eq!(format_source(src, opts(true)), "let o = #{ a: 1, b: 2 };\n");
+    assert_eq!(format_source(src, opts(false)), "let o = #{a: 1, b: 2};\n");
+}
+
+#[test]
+fn test_update_from_str() {
+    let mut opts = Options::default();
+
+    opts.update_from_str(
+        [
+            ("trailing_comma", "preserve"),
+            ("else_on_new_line", "true"),
+            ("binary_op_position", "end"),
+            ("space_in_object_braces", "false"),
+        ]
+        .into_iter(),
+    )
+    .unwrap();
+
+    assert_eq!(opts.trailing_comma, TrailingComma::Preserve);
+    assert!(opts.else_on_new_line);
+    assert_eq!(opts.binary_op_position, OperatorPosition::End);
+    assert!(!opts.space_in_object_braces);
+
+    assert!(opts
+        .update_from_str([("trailing_comma", "sometimes")].into_iter())
+        .is_err());
+}
@@ -0,0 +1,84 @@
+use rhai_fmt::{format_range, Options, TextEdit};
+use rhai_rowan::{parser::Parser, TextRange, TextSize};
+
+fn apply_edits(src: &str, edits: Vec<TextEdit>) -> String {
+    let mut formatted = src.to_string();
+
+    for edit in edits.into_iter().rev() {
+        formatted.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.new_text);
+    }
+
+    formatted
+}
+
+fn format_at(src: &str, marker: &str) -> String {
+    let offset = TextSize::from(src.find(marker).unwrap() as u32);
+    let cst = Parser::new(src).parse_script().into_syntax();
+
+    apply_edits(
+        src,
+        format_range(&cst, TextRange::empty(offset), Options::default()),
+    )
+}
+
+#[test]
+fn test_format_statement_in_block() {
+    let src = r#"fn foo() {
+  let a=1;
+      let b   =  [1,2,3]; // comment
+  let c=3;
+}
+"#;
+
+    assert_eq!(
+        format_at(src, "let b"),
+        r#"fn foo() {
+  let a=1;
+  let b = [1, 2, 3]; // comment
+  let c=3;
+}
+"#
+    );
+}
+
+#[test]
+fn test_format_top_level_statements() {
+    let src = r#"let a=1;
+let b=if a>1{2}else{3};
+"#;
+
+    assert_eq!(
+        format_at(src, "if"),
+        r#"let a=1;
+let b = if a > 1 {
+  2
+} else {
+  3
+};
+"#
+    );
+}
+
+#[test]
+fn test_format_range_multiple_statements() {
+    let src = r#"let a=1;
+
+// comment
+let b=2;
+let c=3;
+"#;
+
+    let cst = Parser::new(src).parse_script().into_syntax();
+    let start = TextSize::from(src.find("let a").unwrap() as u32);
+    let end = TextSize::from(src.find("let b").unwrap() as u32 + 1);
+
+    let edits = format_range(&cst, TextRange::new(start, end), Options::default());
+
+    let untouched = TextSize::from(src.find("let c").unwrap() as u32);
+    assert!(edits.iter().all(|edit| edit.range.end() < untouched));
+
+    assert_eq!(
+        apply_edits(src, edits),
+        "let a = 1;\n\n// comment\nlet b = 2;\nlet c=3;\n"
+    );
+}
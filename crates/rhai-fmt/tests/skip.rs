@@ -0,0 +1,103 @@
+use rhai_fmt::{format_range, format_source, Options};
+use rhai_rowan::{parser::Parser, TextRange, TextSize};
+
+#[test]
+fn test_skip_next_statement() {
+    let src = r#"// rhai-fmt: skip
+let table = [
+    1, 0, 0,
+    0, 1, 0,
+    0, 0, 1,   // identity
+];
+let b=[1,0];
+"#;
+
+    assert_eq!(
+        format_source(src, Options::default()),
+        r#"// rhai-fmt: skip
+let table = [
+    1, 0, 0,
+    0, 1, 0,
+    0, 0, 1,   // identity
+];
+let b = [1, 0];
+"#
+    );
+}
+
+#[test]
+fn test_skip_only_statement_in_fn() {
+    let src = "fn f() {\n    // rhai-fmt: skip\n    [1,\n     2]\n}\n";
+
+    assert_eq!(
+        format_source(src, Options::default()),
+        "fn f() {\n  // rhai-fmt: skip\n  [1,\n     2]\n}\n"
+    );
+}
+
+#[test]
+fn test_off_on_region_in_block() {
+    let src = r#"fn lookup(key) {
+  let a=1;
+  // rhai-fmt: off
+  let map = #{ one:   1,
+               two:   2 };
+  let c =   3; // three
+  // rhai-fmt: on
+  let d=4;
+  map[key]
+}
+"#;
+
+    assert_eq!(
+        format_source(src, Options::default()),
+        r#"fn lookup(key) {
+  let a = 1;
+  // rhai-fmt: off
+  let map = #{ one:   1,
+               two:   2 };
+  let c =   3; // three
+  // rhai-fmt: on
+  let d = 4;
+  map[key]
+}
+"#
+    );
+}
+
+#[test]
+fn test_off_without_on() {
+    let src = "let a=1;\n/* rhai-fmt: off */\nlet b  =  2;\nlet c  =  3;\n";
+
+    assert_eq!(
+        format_source(src, Options::default()),
+        "let a = 1;\n/* rhai-fmt: off */\nlet b  =  2;\nlet c  =  3;\n"
+    );
+}
+
+#[test]
+fn test_format_range_skipped_statement() {
+    let src = "let a=1;\n// rhai-fmt: skip\nlet b  =  [1,\n   2];\n";
+    let cst = Parser::new(src).parse_script().into_syntax();
+    let offset = TextSize::from(src.find("let b").unwrap() as u32);
+
+    assert!(format_range(&cst, TextRange::empty(offset), Options::default()).is_empty());
+}
+
+#[test]
+fn test_format_range_in_disabled_region() {
+    let src = "// rhai-fmt: off\nfn f() {\n  let a   =   1;\n}\n";
+    let cst = Parser::new(src).parse_script().into_syntax();
+    let offset = TextSize::from(src.find("let a").unwrap() as u32);
+
+    assert!(format_range(&cst, TextRange::empty(offset), Options::default()).is_empty());
+}
+
+#[test]
+fn test_format_range_in_skipped_statement() {
+    let src = "// rhai-fmt: skip\nif x {\n  let a   =   1;\n}\n";
+    let cst = Parser::new(src).parse_script().into_syntax();
+    let offset = TextSize::from(src.find("let a").unwrap() as u32);
+
+    assert!(format_range(&cst, TextRange::empty(offset), Options::default()).is_empty());
+}
@@ -0,0 +1,123 @@
+use std::{ffi::OsStr, fs, path::Path};
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+use rhai_hir::Hir;
+use rhai_rowan::{parser::Parser, syntax::SyntaxNode};
+use url::Url;
+
+fn benchmark_scripts() -> Vec<(Url, SyntaxNode)> {
+    let root_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("testdata")
+        .join("benchmarks");
+    let mut entries = fs::read_dir(&root_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some(OsStr::new("rhai")))
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    entries
+        .into_iter()
+        .map(|path| {
+            let url = format!(
+                "file:///benchmarks/{}",
+                path.file_name().unwrap().to_string_lossy()
+            );
+            let source = fs::read_to_string(&path).unwrap();
+
+            (
+                url.parse().unwrap(),
+                Parser::new(&source).parse_script().into_syntax(),
+            )
+        })
+        .collect()
+}
+
+/// Scripts where every script imports the next one,
+/// the last script is imported by all the others through the chain.
+fn import_chain(len: usize) -> Vec<(Url, SyntaxNode)> {
+    (0..len)
+        .map(|i| {
+            let mut source = String::new();
+
+            if i + 1 < len {
+                source += &format!("import \"./chain_{}\" as next;\n\n", i + 1);
+            }
+
+            source += &format!("fn value_{i}(x) {{\n    x + {i}\n}}\n\n");
+
+            if i + 1 < len {
+                source += &format!("let value = next::value_{}({i});\n", i + 1);
+            } else {
+                source += &format!("let value = value_{i}({i});\n");
+            }
+
+            (
+                format!("file:///chain/chain_{i}.rhai").parse().unwrap(),
+                Parser::new(&source).parse_script().into_syntax(),
+            )
+        })
+        .collect()
+}
+
+/// Compare resolving everything with resolving only the changes
+/// after a single script is edited, as it happens in an editor.
+fn bench_edit(c: &mut Criterion, name: &str, scripts: &[(Url, SyntaxNode)], edited: usize) {
+    let mut hir = Hir::new();
+
+    for (url, syntax) in scripts {
+        hir.add_source(url, syntax);
+    }
+
+    hir.resolve_all();
+
+    let (edited_url, edited_syntax) = &scripts[edited];
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("resolve all", |b| {
+        b.iter_batched(
+            || hir.clone(),
+            |mut hir| {
+                hir.add_source(edited_url, edited_syntax);
+                hir.resolve_all();
+                black_box(hir)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("resolve changed", |b| {
+        b.iter_batched(
+            || hir.clone(),
+            |mut hir| {
+                hir.add_source(edited_url, edited_syntax);
+                hir.resolve_changed();
+                black_box(hir)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    // The scripts do not import each other.
+    let scripts = benchmark_scripts();
+    bench_edit(c, "resolve-after-edit", &scripts, 0);
+
+    // The importers of the edited script in the chain
+    // are resolved again, the other scripts are not.
+    let mut scripts = scripts;
+    scripts.extend(import_chain(25));
+    let edited = scripts.len() - 1;
+    bench_edit(c, "resolve-after-edit-import-chain", &scripts, edited);
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = criterion_benchmark
+);
+criterion_main!(benches);
@@ -1,4 +1,6 @@
 use crate::Symbol;
+use rhai_rowan::TextRange;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Error)]
@@ -7,7 +9,12 @@ pub struct Error {
     pub kind: ErrorKind,
 }
 
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
 pub enum ErrorKind {
     #[error("duplicate function parameter")]
     DuplicateFnParameter {
@@ -31,6 +38,145 @@ pub enum ErrorKind {
     },
     #[error("unresolved import")]
     UnresolvedImport { import: Symbol },
+    /// The imports in `cycle` lead back to the module
+    /// that contains `import`.
+    #[error("circular import")]
+    CircularImport { import: Symbol, cycle: Vec<Symbol> },
     #[error("nested functions are not allowed")]
     NestedFunction { function: Symbol },
+    #[error(
+        "expected {}{expected} argument{}, found {found}",
+        if *variadic { "at least " } else { "" },
+        if *expected == 1 { "" } else { "s" }
+    )]
+    ArgumentCountMismatch {
+        call: Symbol,
+        function: Symbol,
+        expected: usize,
+        variadic: bool,
+        found: usize,
+    },
+    #[error("mismatched argument type, expected `{expected}`, found `{found}`")]
+    ArgumentTypeMismatch {
+        argument: Symbol,
+        function: Symbol,
+        param: Symbol,
+        expected: String,
+        found: String,
+    },
+    #[error(
+        "no operator `{operator}` for {}",
+        match &rhs {
+            Some(rhs) => format!("types `{lhs}` and `{rhs}`"),
+            None => format!("type `{lhs}`"),
+        }
+    )]
+    OperandTypeMismatch {
+        expr: Symbol,
+        operator: String,
+        lhs: String,
+        rhs: Option<String>,
+    },
+    #[error("mismatched return type, expected `{expected}`, found `{found}`")]
+    ReturnTypeMismatch {
+        expr: Symbol,
+        declaration: Symbol,
+        expected: String,
+        found: String,
+    },
+    #[error("unused variable `{name}`")]
+    UnusedVariable { variable: Symbol, name: String },
+    #[error("unused parameter `{name}`")]
+    UnusedParameter { param: Symbol, name: String },
+    #[error("unused function `{name}`")]
+    UnusedFunction { function: Symbol, name: String },
+    #[error("unused import `{name}`")]
+    UnusedImport { alias: Symbol, name: String },
+    #[error("`{code}` is not suppressed here")]
+    UnusedSuppression { range: TextRange, code: String },
+}
+
+impl ErrorKind {
+    /// All the codes returned by [`ErrorKind::code`].
+    pub const CODES: &'static [&'static str] = &[
+        "duplicate-fn-parameter",
+        "unresolved-reference",
+        "unresolved-import",
+        "circular-import",
+        "nested-function",
+        "argument-count-mismatch",
+        "argument-type-mismatch",
+        "operand-type-mismatch",
+        "return-type-mismatch",
+        "unused-variable",
+        "unused-parameter",
+        "unused-function",
+        "unused-import",
+        "unused-suppression",
+    ];
+
+    /// A stable code that identifies the kind of the error.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DuplicateFnParameter { .. } => "duplicate-fn-parameter",
+            Self::UnresolvedReference { .. } => "unresolved-reference",
+            Self::UnresolvedImport { .. } => "unresolved-import",
+            Self::CircularImport { .. } => "circular-import",
+            Self::NestedFunction { .. } => "nested-function",
+            Self::ArgumentCountMismatch { .. } => "argument-count-mismatch",
+            Self::ArgumentTypeMismatch { .. } => "argument-type-mismatch",
+            Self::OperandTypeMismatch { .. } => "operand-type-mismatch",
+            Self::ReturnTypeMismatch { .. } => "return-type-mismatch",
+            Self::UnusedVariable { .. } => "unused-variable",
+            Self::UnusedParameter { .. } => "unused-parameter",
+            Self::UnusedFunction { .. } => "unused-function",
+            Self::UnusedImport { .. } => "unused-import",
+            Self::UnusedSuppression { .. } => "unused-suppression",
+        }
+    }
+
+    /// The symbol the error should be reported at.
+    ///
+    /// Errors that are not about a symbol return `None`.
+    #[must_use]
+    pub fn symbol(&self) -> Option<Symbol> {
+        match self {
+            Self::DuplicateFnParameter {
+                duplicate_symbol, ..
+            } => Some(*duplicate_symbol),
+            Self::UnresolvedReference {
+                reference_symbol, ..
+            } => Some(*reference_symbol),
+            Self::UnresolvedImport { import } | Self::CircularImport { import, .. } => {
+                Some(*import)
+            }
+            Self::NestedFunction { function } | Self::UnusedFunction { function, .. } => {
+                Some(*function)
+            }
+            Self::ArgumentCountMismatch { call, .. } => Some(*call),
+            Self::ArgumentTypeMismatch { argument, .. } => Some(*argument),
+            Self::OperandTypeMismatch { expr, .. } | Self::ReturnTypeMismatch { expr, .. } => {
+                Some(*expr)
+            }
+            Self::UnusedVariable { variable, .. } => Some(*variable),
+            Self::UnusedParameter { param, .. } => Some(*param),
+            Self::UnusedImport { alias, .. } => Some(*alias),
+            Self::UnusedSuppression { .. } => None,
+        }
+    }
+
+    /// Whether the error is about unused code
+    /// rather than something that is wrong.
+    #[must_use]
+    pub fn is_unused(&self) -> bool {
+        matches!(
+            self,
+            Self::UnusedVariable { .. }
+                | Self::UnusedParameter { .. }
+                | Self::UnusedFunction { .. }
+                | Self::UnusedImport { .. }
+                | Self::UnusedSuppression { .. }
+        )
+    }
 }
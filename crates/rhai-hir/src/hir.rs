@@ -9,6 +9,7 @@ use std::sync::Arc;
 
 use crate::{
     module::{ModuleData, ModuleResolver, DefaultModuleResolver},
+    hir::resolve::PendingChanges,
     scope::ScopeData,
     source::{Source, SourceData},
     symbol::*,
@@ -17,10 +18,15 @@ use crate::{
 };
 
 use rhai_rowan::syntax::SyntaxNode;
+use serde::{Deserialize, Serialize};
 use slotmap::{Key, SlotMap};
 use url::Url;
 
-#[derive(Clone)]
+/// The HIR can be serialized to cache it.
+///
+/// The module resolver is not serialized, it is reset to
+/// the default one and has to be set again if needed.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Hir {
     pub(crate) static_module: Module,
     pub(crate) virtual_source: Source,
@@ -30,7 +36,14 @@ pub struct Hir {
     pub(crate) sources: SlotMap<Source, SourceData>,
     pub(crate) types: SlotMap<Type, TypeData>,
     pub(crate) builtin_types: BuiltinTypes,
-    pub(crate) module_resolver: Arc<dyn ModuleResolver>
+    #[serde(skip, default = "default_module_resolver")]
+    pub(crate) module_resolver: Arc<dyn ModuleResolver>,
+    #[serde(skip)]
+    pub(crate) pending: PendingChanges,
+}
+
+fn default_module_resolver() -> Arc<dyn ModuleResolver> {
+    Arc::new(DefaultModuleResolver)
 }
 
 impl Default for Hir {
@@ -44,7 +57,8 @@ impl Default for Hir {
             sources: Default::default(),
             types: Default::default(),
             builtin_types: BuiltinTypes::uninit(),
-            module_resolver: Arc::new(DefaultModuleResolver)
+            module_resolver: default_module_resolver(),
+            pending: PendingChanges::default(),
         };
         this.prepare();
         this
@@ -61,6 +75,8 @@ impl Hir {
 
     pub fn set_import_resolver(&mut self, resolver: impl ModuleResolver + 'static) {
         self.module_resolver = Arc::new(resolver);
+        // Imports might resolve differently.
+        self.pending.modules = true;
     }
 }
 
@@ -73,6 +89,7 @@ impl Hir {
         self.types.clear();
         self.builtin_types = BuiltinTypes::uninit();
         self.static_module = Module::null();
+        self.pending = PendingChanges::default();
         self.prepare();
     }
 
@@ -226,7 +243,7 @@ impl ops::Index<Type> for Hir {
 /// but always exist in the HIR and cannot be removed.
 ///
 /// This struct keeps track of their keys.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BuiltinTypes {
     pub module: Type,
     pub int: Type,
@@ -215,6 +215,8 @@ impl Hir {
                     ..ScopeData::default()
                 });
 
+                let mut params = Vec::new();
+
                 if let Some(param_list) = expr.typed_param_list() {
                     for param in param_list.params() {
                         let param_ty = param.ty().map(|t| self.add_type(source, None, &t));
@@ -233,6 +235,7 @@ impl Hir {
                                     .map(|s| s.text().to_string())
                                     .unwrap_or_default(),
                                 is_param: true,
+                                is_rest: param.op_spread_token().is_some(),
                                 ty_decl: param_ty,
                                 ..DeclSymbol::default()
                             })),
@@ -240,6 +243,7 @@ impl Hir {
                         });
 
                         fn_scope.add_symbol(self, symbol, false);
+                        params.push(symbol);
                     }
                 }
 
@@ -263,6 +267,7 @@ impl Hir {
                             .unwrap_or_default(),
                         docs,
                         scope: fn_scope,
+                        params,
                         getter: expr.has_kw_get(),
                         setter: expr.has_kw_set(),
                         is_def: true,
@@ -4,6 +4,7 @@ use crate::{
     module::{ModuleKind, STATIC_URL_SCHEME},
     scope::ScopeParent,
     source::SourceKind,
+    suppression::collect_suppressions,
     TypeKind,
 };
 use rhai_rowan::{
@@ -20,6 +21,8 @@ impl Hir {
     pub fn add_source(&mut self, url: &Url, syntax: &SyntaxNode) {
         if let Some(s) = self.source_of(url) {
             self.remove_source(s);
+        } else {
+            self.pending.modules = true;
         }
 
         if let Some(rhai) = Rhai::cast(syntax.clone()) {
@@ -27,9 +30,11 @@ impl Hir {
                 kind: SourceKind::Script,
                 url: url.clone(),
                 module: Module::null(),
+                suppressions: collect_suppressions(syntax),
             });
 
             self.add_script(source, &rhai);
+            self.pending.sources.insert(source);
         }
 
         if let Some(def) = RhaiDef::cast(syntax.clone()) {
@@ -37,9 +42,11 @@ impl Hir {
                 kind: SourceKind::Def,
                 url: url.clone(),
                 module: Module::null(),
+                suppressions: collect_suppressions(syntax),
             });
 
             self.add_def(source, &def);
+            self.pending.definitions = true;
         }
     }
 }
@@ -64,6 +71,7 @@ impl Hir {
                 url: "rhai-virtual:///".parse().unwrap(),
                 kind: SourceKind::Def,
                 module: self.static_module,
+                suppressions: Vec::new(),
             });
             self.virtual_source = source;
         }
@@ -928,6 +928,8 @@ impl Hir {
                     docs = fn_item.docs_content().replace("rhai-scope", "rhai");
                 }
 
+                let mut params = Vec::new();
+
                 if let Some(param_list) = expr.param_list() {
                     for param in param_list.params() {
                         let symbol = self.add_symbol(SymbolData {
@@ -950,6 +952,7 @@ impl Hir {
                         });
 
                         fn_scope.add_symbol(self, symbol, false);
+                        params.push(symbol);
                     }
                 }
 
@@ -971,6 +974,7 @@ impl Hir {
                             .unwrap_or_default(),
                         docs,
                         scope: fn_scope,
+                        params,
                         ..FnSymbol::default()
                     }),
                     ty: self.builtin_types.unknown,
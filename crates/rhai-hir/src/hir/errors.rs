@@ -1,37 +1,145 @@
 use crate::{
     error::{Error, ErrorKind},
     source::Source,
-    symbol::SymbolKind,
-    HashMap, Hir, Symbol,
+    symbol::{BinarySymbol, CallSymbol, FnSymbol, SymbolKind, UnarySymbol},
+    ty::Type,
+    HashMap, HashSet, Hir, IndexMap, Symbol,
 };
+use rhai_rowan::TextRange;
 
 impl Hir {
+    /// Errors in all sources without the ones
+    /// suppressed by comments.
     #[must_use]
     pub fn errors(&self) -> Vec<Error> {
-        let mut errors = Vec::new();
+        let mut by_source: IndexMap<Option<Source>, Vec<Error>> = self
+            .sources()
+            .map(|(source, _)| (Some(source), Vec::new()))
+            .collect();
+
+        let method_calls = self.method_call_names();
+        let no_method_calls = HashSet::default();
 
-        for (symbol, _) in self.symbols() {
-            self.collect_errors_from_symbol(symbol, &mut errors);
+        for (symbol, symbol_data) in self.symbols() {
+            self.collect_errors_from_symbol(
+                symbol,
+                method_calls
+                    .get(&symbol_data.source.source)
+                    .unwrap_or(&no_method_calls),
+                by_source.entry(symbol_data.source.source).or_default(),
+            );
         }
 
-        errors
+        let mut cycles = Vec::new();
+        self.collect_circular_import_errors(None, &mut cycles);
+
+        for error in cycles {
+            let source = error.kind.symbol().and_then(|sym| self[sym].source.source);
+            by_source.entry(source).or_default().push(error);
+        }
+
+        by_source
+            .into_iter()
+            .flat_map(|(source, errors)| match source {
+                Some(source) => self.suppress_errors(source, errors),
+                None => errors,
+            })
+            .collect()
     }
 
+    /// Errors in the given source without the ones
+    /// suppressed by comments.
     #[must_use]
     pub fn errors_for_source(&self, source: Source) -> Vec<Error> {
         let mut errors = Vec::new();
 
+        let method_calls = self
+            .method_call_names()
+            .remove(&Some(source))
+            .unwrap_or_default();
+
         for (symbol, _) in self
             .symbols()
             .filter(|(_, symbol_data)| symbol_data.source.source == Some(source))
         {
-            self.collect_errors_from_symbol(symbol, &mut errors);
+            self.collect_errors_from_symbol(symbol, &method_calls, &mut errors);
+        }
+
+        self.collect_circular_import_errors(Some(source), &mut errors);
+
+        self.suppress_errors(source, errors)
+    }
+
+    /// The range in its source the error should be reported at.
+    #[must_use]
+    pub fn error_range(&self, error: &Error) -> Option<TextRange> {
+        match &error.kind {
+            ErrorKind::UnusedSuppression { range, .. } => Some(*range),
+            kind => self.symbol(kind.symbol()?)?.selection_or_text_range(),
+        }
+    }
+
+    /// Remove errors that are suppressed by comments,
+    /// and report suppressions that are not needed.
+    fn suppress_errors(&self, source: Source, errors: Vec<Error>) -> Vec<Error> {
+        let suppressions = &self[source].suppressions;
+
+        if suppressions.is_empty() {
+            return errors;
+        }
+
+        let mut used = HashSet::new();
+
+        let mut errors = errors
+            .into_iter()
+            .filter(|error| {
+                let Some(range) = self.error_range(error) else {
+                    return true;
+                };
+
+                let mut suppressed = false;
+
+                for (idx, suppression) in suppressions.iter().enumerate() {
+                    if !suppression.covers(range) {
+                        continue;
+                    }
+
+                    if let Some(code) = suppression
+                        .codes
+                        .iter()
+                        .find(|&code| code == error.kind.code())
+                    {
+                        used.insert((idx, code.as_str()));
+                        suppressed = true;
+                    }
+                }
+
+                !suppressed
+            })
+            .collect::<Vec<_>>();
+
+        for (idx, suppression) in suppressions.iter().enumerate() {
+            for code in &suppression.codes {
+                if !used.contains(&(idx, code.as_str())) {
+                    errors.push(Error {
+                        kind: ErrorKind::UnusedSuppression {
+                            range: suppression.text_range,
+                            code: code.clone(),
+                        },
+                    });
+                }
+            }
         }
 
         errors
     }
 
-    fn collect_errors_from_symbol(&self, symbol: Symbol, errors: &mut Vec<Error>) {
+    fn collect_errors_from_symbol(
+        &self,
+        symbol: Symbol,
+        method_calls: &HashSet<&str>,
+        errors: &mut Vec<Error>,
+    ) {
         if let Some(symbol_data) = self.symbol(symbol) {
             match &symbol_data.kind {
                 SymbolKind::Ref(r) => {
@@ -44,11 +152,14 @@ impl Hir {
                         });
                     }
                 }
+                SymbolKind::Decl(_) => self.collect_unused_errors(symbol, method_calls, errors),
                 SymbolKind::Fn(f) => {
                     if f.is_def {
                         return;
                     }
 
+                    self.collect_unused_errors(symbol, method_calls, errors);
+
                     let top_level = self
                         .modules
                         .iter()
@@ -82,7 +193,12 @@ impl Hir {
                             }
                         }
                     }
+
+                    self.collect_return_type_errors(symbol, f, errors);
                 }
+                SymbolKind::Call(call) => self.collect_call_errors(symbol, call, errors),
+                SymbolKind::Binary(binary) => self.collect_binary_errors(symbol, binary, errors),
+                SymbolKind::Unary(unary) => self.collect_unary_errors(symbol, unary, errors),
                 SymbolKind::Import(import) => {
                     if import.target.is_none() {
                         errors.push(Error {
@@ -94,4 +210,334 @@ impl Hir {
             }
         }
     }
+
+    /// Imports that are part of a cycle, optionally only in the given source.
+    fn collect_circular_import_errors(&self, source: Option<Source>, errors: &mut Vec<Error>) {
+        for cycle in self.import_cycles() {
+            let import = cycle[0].import;
+
+            if source.is_some() && self[import].source.source != source {
+                continue;
+            }
+
+            errors.push(Error {
+                kind: ErrorKind::CircularImport {
+                    import,
+                    cycle: cycle.iter().skip(1).map(|i| i.import).collect(),
+                },
+            });
+        }
+    }
+
+    /// Check calls of functions from definitions against their signatures.
+    fn collect_call_errors(&self, symbol: Symbol, call: &CallSymbol, errors: &mut Vec<Error>) {
+        let Some(function) = self.call_target(call) else {
+            return;
+        };
+
+        let Some(f) = self[function].kind.as_fn() else {
+            return;
+        };
+
+        if !f.is_def {
+            return;
+        }
+
+        let arg_types = call
+            .arguments
+            .iter()
+            .map(|&arg| self[arg].ty)
+            .collect::<Vec<_>>();
+
+        let mut arity_matched = None;
+
+        for overload in self.overloads(function) {
+            let Some(o) = self[overload].kind.as_fn() else {
+                continue;
+            };
+
+            if !o.accepts_arg_count(self, arg_types.len()) {
+                continue;
+            }
+
+            match self.first_mismatched_argument(o, &arg_types) {
+                Some(mismatch) => {
+                    arity_matched.get_or_insert((overload, mismatch));
+                }
+                None => return,
+            }
+        }
+
+        if let Some((overload, (arg_idx, param))) = arity_matched {
+            let argument = call.arguments[arg_idx];
+            let expected = self[param]
+                .kind
+                .as_decl()
+                .and_then(|d| d.ty_decl)
+                .unwrap_or(self[param].ty);
+
+            errors.push(Error {
+                kind: ErrorKind::ArgumentTypeMismatch {
+                    argument,
+                    function: overload,
+                    param,
+                    expected: expected.fmt(self).to_string(),
+                    found: self[argument].ty.fmt(self).to_string(),
+                },
+            });
+            return;
+        }
+
+        errors.push(Error {
+            kind: ErrorKind::ArgumentCountMismatch {
+                call: symbol,
+                function,
+                expected: f.required_param_count(self),
+                variadic: f.is_variadic(self),
+                found: arg_types.len(),
+            },
+        });
+    }
+
+    /// Check operands of binary operators declared in definitions.
+    fn collect_binary_errors(
+        &self,
+        symbol: Symbol,
+        binary: &BinarySymbol,
+        errors: &mut Vec<Error>,
+    ) {
+        if binary.is_field_access() {
+            return;
+        }
+
+        let (Some(lhs), Some(rhs)) = (binary.lhs, binary.rhs) else {
+            return;
+        };
+
+        let (lhs_ty, rhs_ty) = (self[lhs].ty, self[rhs].ty);
+
+        let mut ops = self
+            .operators()
+            .filter(|op| op.name == binary.lookup_text && op.rhs_ty.is_some())
+            .peekable();
+
+        if ops.peek().is_none() {
+            return;
+        }
+
+        let matched = ops.any(|op| {
+            lhs_ty.is_assignable_to(self, op.lhs_ty)
+                && op
+                    .rhs_ty
+                    .is_some_and(|op_rhs| rhs_ty.is_assignable_to(self, op_rhs))
+        });
+
+        if !matched {
+            errors.push(Error {
+                kind: ErrorKind::OperandTypeMismatch {
+                    expr: symbol,
+                    operator: binary.lookup_text.clone(),
+                    lhs: lhs_ty.fmt(self).to_string(),
+                    rhs: Some(rhs_ty.fmt(self).to_string()),
+                },
+            });
+        }
+    }
+
+    /// Check operands of unary operators declared in definitions.
+    fn collect_unary_errors(&self, symbol: Symbol, unary: &UnarySymbol, errors: &mut Vec<Error>) {
+        let Some(rhs) = unary.rhs else {
+            return;
+        };
+
+        let rhs_ty = self[rhs].ty;
+
+        let mut ops = self
+            .operators()
+            .filter(|op| op.name == unary.lookup_text && op.rhs_ty.is_none())
+            .peekable();
+
+        if ops.peek().is_none() {
+            return;
+        }
+
+        if !ops.any(|op| rhs_ty.is_assignable_to(self, op.lhs_ty)) {
+            errors.push(Error {
+                kind: ErrorKind::OperandTypeMismatch {
+                    expr: symbol,
+                    operator: unary.lookup_text.clone(),
+                    lhs: rhs_ty.fmt(self).to_string(),
+                    rhs: None,
+                },
+            });
+        }
+    }
+
+    /// Check the returned values of script functions that
+    /// are also declared with a return type in definitions.
+    fn collect_return_type_errors(&self, symbol: Symbol, f: &FnSymbol, errors: &mut Vec<Error>) {
+        let declaration = self.visible_symbols_from_symbol(symbol).find(|&sym| {
+            self[sym].kind.as_fn().is_some_and(|decl| {
+                decl.is_def
+                    && decl.name == f.name
+                    && !self[decl.ret_ty].kind.is_unknown()
+                    && decl.params.len() == f.params.len()
+            })
+        });
+
+        let Some(declaration) = declaration else {
+            return;
+        };
+
+        let expected = self[declaration].kind.as_fn().unwrap().ret_ty;
+
+        let mut returned = self
+            .descendant_symbols(f.scope)
+            .filter_map(|sym| {
+                let ret = self[sym].kind.as_return()?;
+
+                if self.containing_fn(sym, true) != Some(symbol) {
+                    return None;
+                }
+
+                Some(match ret.expr {
+                    Some(expr) => (expr, self[expr].ty),
+                    None => (sym, self.builtin_types.void),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(&tail) = self[f.scope].symbols.last() {
+            let tail_data = &self[tail];
+
+            if !tail_data.is_param()
+                && !matches!(
+                    tail_data.kind,
+                    SymbolKind::Decl(_) | SymbolKind::Return(_) | SymbolKind::Fn(_)
+                )
+                && !self[tail_data.ty].kind.is_void()
+                && !self[tail_data.ty].kind.is_never()
+            {
+                returned.push((tail, tail_data.ty));
+            }
+        }
+
+        for (expr, ty) in returned {
+            if !ty.is_assignable_to(self, expected) {
+                errors.push(Error {
+                    kind: ErrorKind::ReturnTypeMismatch {
+                        expr,
+                        declaration,
+                        expected: expected.fmt(self).to_string(),
+                        found: ty.fmt(self).to_string(),
+                    },
+                });
+            }
+        }
+    }
+
+    /// Report declarations in scripts that are never referenced,
+    /// names starting with `_` are intentionally unused.
+    fn collect_unused_errors(
+        &self,
+        symbol: Symbol,
+        method_calls: &HashSet<&str>,
+        errors: &mut Vec<Error>,
+    ) {
+        let symbol_data = &self[symbol];
+
+        if !symbol_data
+            .source
+            .source
+            .is_some_and(|source| self[source].kind.is_script())
+        {
+            return;
+        }
+
+        let (name, references) = match &symbol_data.kind {
+            SymbolKind::Fn(f) => (&f.name, &f.references),
+            SymbolKind::Decl(d) => (&d.name, &d.references),
+            _ => return,
+        };
+
+        if name.is_empty() || name.starts_with('_') || !references.is_empty() {
+            return;
+        }
+
+        let name = name.clone();
+
+        let kind = match &symbol_data.kind {
+            // Public functions can be called from other modules,
+            // and any method call with the name might call the function.
+            SymbolKind::Fn(_) if !symbol_data.export && !method_calls.contains(name.as_str()) => {
+                ErrorKind::UnusedFunction {
+                    function: symbol,
+                    name,
+                }
+            }
+            SymbolKind::Decl(d) if d.is_import => ErrorKind::UnusedImport {
+                alias: symbol,
+                name,
+            },
+            SymbolKind::Decl(d) if d.is_param => ErrorKind::UnusedParameter {
+                param: symbol,
+                name,
+            },
+            SymbolKind::Decl(d) if !d.is_pat && !self.is_exported(symbol) => {
+                ErrorKind::UnusedVariable {
+                    variable: symbol,
+                    name,
+                }
+            }
+            _ => return,
+        };
+
+        errors.push(Error { kind });
+    }
+
+    /// Names of field accesses by source,
+    /// method calls are field accesses that are not resolved.
+    fn method_call_names(&self) -> HashMap<Option<Source>, HashSet<&str>> {
+        let mut names: HashMap<Option<Source>, HashSet<&str>> = HashMap::default();
+
+        for (_, data) in self.symbols() {
+            if let Some(r) = data.kind.as_reference().filter(|r| r.field_access) {
+                names
+                    .entry(data.source.source)
+                    .or_default()
+                    .insert(r.name.as_str());
+            }
+        }
+
+        names
+    }
+
+    /// Whether the declaration is the target of an `export` statement.
+    fn is_exported(&self, symbol: Symbol) -> bool {
+        self.scope_symbols(self[symbol].parent_scope).any(|sym| {
+            self[sym]
+                .kind
+                .as_export()
+                .is_some_and(|export| export.target == Some(symbol))
+        })
+    }
+
+    /// Find the first argument that does not match its parameter type.
+    fn first_mismatched_argument(
+        &self,
+        f: &FnSymbol,
+        arg_types: &[Type],
+    ) -> Option<(usize, Symbol)> {
+        arg_types.iter().enumerate().find_map(|(idx, &arg_ty)| {
+            let param = f.param_for_arg(self, idx)?;
+
+            let param_ty = self[param].kind.as_decl()?.ty_decl?;
+
+            if arg_ty.is_assignable_to(self, param_ty) {
+                None
+            } else {
+                Some((idx, param))
+            }
+        })
+    }
 }
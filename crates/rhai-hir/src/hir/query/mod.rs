@@ -2,6 +2,7 @@ use rhai_rowan::{parser, util::is_valid_ident, TextRange, TextSize};
 use std::cmp::Ordering;
 
 use super::*;
+use crate::scope::ScopeParent;
 
 pub mod modules;
 pub mod scope_iter;
@@ -111,6 +112,135 @@ impl Hir {
     pub fn operator_by_name(&self, name: &str) -> Option<&OpSymbol> {
         self.operators().find(|&op| op.name == name)
     }
+
+    /// All overloads of the given function including itself
+    /// in the order of declaration.
+    ///
+    /// Overloads are functions with the same name in the same scope.
+    pub fn overloads(&self, function: Symbol) -> impl Iterator<Item = Symbol> {
+        let f = self[function].kind.as_fn();
+
+        let mut overloads = self
+            .scope_symbols(self[function].parent_scope)
+            .filter(|&sym| match (f, self[sym].kind.as_fn()) {
+                (Some(f), Some(o)) => {
+                    o.name == f.name && o.getter == f.getter && o.setter == f.setter
+                }
+                _ => sym == function,
+            })
+            .collect::<Vec<_>>();
+
+        // Hoisted symbols are not ordered.
+        overloads.sort_by_key(|&sym| {
+            let source = &self[sym].source;
+            (source.source, source.text_range.map(TextRange::start))
+        });
+
+        overloads.into_iter()
+    }
+
+    /// Select the overload of the function that fits
+    /// the given argument types best.
+    ///
+    /// Only overloads that accept the amount of arguments are considered,
+    /// preferring the ones where all arguments are assignable to the parameters,
+    /// then the ones with the most exact parameter type matches,
+    /// then the ones with the most arguments not passed as `...rest`
+    /// and finally the ones that are not variadic.
+    ///
+    /// Returns `None` if no overload accepts the amount of arguments.
+    #[must_use]
+    pub fn select_overload(&self, function: Symbol, arg_types: &[Type]) -> Option<Symbol> {
+        self.overloads(function)
+            .filter_map(|overload| {
+                let f = self[overload].kind.as_fn()?;
+
+                if !f.accepts_arg_count(self, arg_types.len()) {
+                    return None;
+                }
+
+                let mut assignable = true;
+                let mut exact_count = 0_usize;
+
+                for (idx, &arg_ty) in arg_types.iter().enumerate() {
+                    let Some(param_ty) = f
+                        .param_for_arg(self, idx)
+                        .and_then(|p| self[p].kind.as_decl()?.ty_decl)
+                    else {
+                        continue;
+                    };
+
+                    if arg_ty.is(self, param_ty, true) {
+                        exact_count += 1;
+                    } else if !arg_ty.is_assignable_to(self, param_ty) {
+                        assignable = false;
+                    }
+                }
+
+                let named_count = arg_types.len().min(f.required_param_count(self));
+
+                Some((
+                    overload,
+                    (assignable, exact_count, named_count, !f.is_variadic(self)),
+                ))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            // `max_by_key` returns the last maximum,
+            // but the first declared overload is preferred.
+            .rev()
+            .max_by_key(|&(_, score)| score)
+            .map(|(overload, _)| overload)
+    }
+
+    /// The reference symbol that is called in a call expression,
+    /// for paths this is the last segment.
+    #[must_use]
+    pub fn call_reference(&self, call: &CallSymbol) -> Option<Symbol> {
+        let lhs = call.lhs?;
+
+        match &self[lhs].kind {
+            SymbolKind::Path(path) => path.segments.last().copied(),
+            SymbolKind::Ref(_) => Some(lhs),
+            _ => None,
+        }
+    }
+
+    /// The function that is called in a call expression.
+    #[must_use]
+    pub fn call_target(&self, call: &CallSymbol) -> Option<Symbol> {
+        let reference = self.call_reference(call)?;
+
+        match self[reference].target()? {
+            ReferenceTarget::Symbol(target) if self[target].kind.is_fn() => Some(target),
+            _ => None,
+        }
+    }
+
+    /// The innermost function definition the symbol is part of.
+    ///
+    /// If `closures` is set, closures count as functions as well,
+    /// otherwise they are considered part of the function they are in.
+    ///
+    /// Returns `None` for symbols at the top level of a source.
+    #[must_use]
+    pub fn containing_fn(&self, symbol: Symbol, closures: bool) -> Option<Symbol> {
+        let mut scope = self.symbol(symbol)?.parent_scope;
+
+        loop {
+            match self.scope(scope)?.parent? {
+                ScopeParent::Scope(parent) => scope = parent,
+                ScopeParent::Symbol(parent) => {
+                    match self[parent].kind {
+                        SymbolKind::Fn(_) => return Some(parent),
+                        SymbolKind::Closure(_) if closures => return Some(parent),
+                        _ => {}
+                    }
+                    scope = self[parent].parent_scope;
+                }
+            }
+        }
+    }
 }
 
 /// Used for filtering shadowed symbols.
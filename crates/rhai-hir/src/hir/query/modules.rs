@@ -1,4 +1,6 @@
-use crate::scope::ScopeParent;
+use std::collections::VecDeque;
+
+use crate::{module::ModuleImport, scope::ScopeParent, HashMap};
 
 use super::*;
 
@@ -88,6 +90,44 @@ impl Hir {
         missing.into_iter()
     }
 
+    /// The module dependency graph as the resolved imports
+    /// between modules.
+    #[must_use]
+    pub fn module_imports(&self) -> Vec<ModuleImport> {
+        self.symbols
+            .iter()
+            .filter_map(|(symbol, data)| {
+                let import = data.kind.as_import()?;
+
+                Some(ModuleImport {
+                    from: self.module_by_symbol(symbol)?,
+                    to: import.target?,
+                    import: symbol,
+                })
+            })
+            .collect()
+    }
+
+    /// The import cycles between modules, one for every import
+    /// that is part of a cycle.
+    ///
+    /// Every cycle starts with its import and is followed
+    /// by the imports that lead back to the module of it.
+    #[must_use]
+    pub fn import_cycles(&self) -> Vec<Vec<ModuleImport>> {
+        let imports = self.module_imports();
+
+        let mut edges: HashMap<Module, Vec<ModuleImport>> = HashMap::new();
+        for &i in &imports {
+            edges.entry(i.from).or_default().push(i);
+        }
+
+        imports
+            .iter()
+            .filter_map(|&first| import_cycle(&edges, first))
+            .collect()
+    }
+
     /// Resolve a symbol in a module.
     #[must_use]
     pub fn find_in_module(&self, module: Module, name: &str) -> Option<Symbol> {
@@ -118,3 +158,39 @@ impl Hir {
         None
     }
 }
+
+/// Breadth-first search for the shortest path from the target
+/// of the import back to the module of it.
+fn import_cycle(
+    edges: &HashMap<Module, Vec<ModuleImport>>,
+    first: ModuleImport,
+) -> Option<Vec<ModuleImport>> {
+    let mut reached_by: HashMap<Module, ModuleImport> = HashMap::new();
+    let mut queue = VecDeque::from([first.to]);
+
+    while let Some(module) = queue.pop_front() {
+        if module == first.from {
+            let mut cycle = Vec::new();
+            let mut current = module;
+
+            while current != first.to {
+                let edge = reached_by[&current];
+                cycle.push(edge);
+                current = edge.from;
+            }
+
+            cycle.push(first);
+            cycle.reverse();
+            return Some(cycle);
+        }
+
+        for &edge in edges.get(&module).into_iter().flatten() {
+            if edge.to != first.to && !reached_by.contains_key(&edge.to) {
+                reached_by.insert(edge.to, edge);
+                queue.push_back(edge.to);
+            }
+        }
+    }
+
+    None
+}
@@ -8,6 +8,7 @@ use crate::{
 
 impl Hir {
     pub fn remove_source(&mut self, source: Source) {
+        self.pending_removal(source);
         self.sources.remove(source);
 
         let symbols_to_remove = self
@@ -39,6 +40,37 @@ impl Hir {
         self.cleanup_modules();
     }
 
+    /// Record the sources that have to be resolved again
+    /// after the source is removed.
+    fn pending_removal(&mut self, source: Source) {
+        let Some(source_data) = self.sources.get(source) else {
+            return;
+        };
+
+        self.pending.sources.remove(&source);
+
+        if source_data.kind.is_def() {
+            self.pending.definitions = true;
+            return;
+        }
+
+        let module = source_data.module;
+
+        let importers = self
+            .symbols
+            .values()
+            .filter(|data| {
+                data.kind
+                    .as_import()
+                    .is_some_and(|import| import.target == Some(module))
+            })
+            .filter_map(|data| data.source.source)
+            .filter(|&importer| importer != source)
+            .collect::<Vec<_>>();
+
+        self.pending.sources.extend(importers);
+    }
+
     /// Remove scopes and symbols of modules that
     /// are not protected and have no sources associated to them.
     fn cleanup_modules(&mut self) {
@@ -1,11 +1,27 @@
+use std::collections::VecDeque;
+
 use crate::{
-    symbol::{ReferenceTarget, SymbolKind, VirtualSymbol},
-    Hir, Module, Symbol,
+    source::Source,
+    symbol::{ReferenceTarget, SymbolData, SymbolKind, VirtualSymbol},
+    HashMap, HashSet, Hir, Module, Symbol,
 };
 use itertools::Itertools;
 
 mod types;
 
+/// Changes since the last resolution,
+/// used by [`Hir::resolve_changed`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PendingChanges {
+    /// Sources that were added or that import removed modules.
+    pub(crate) sources: HashSet<Source>,
+    /// New modules were added, imports might resolve differently.
+    pub(crate) modules: bool,
+    /// Definitions were added or removed,
+    /// they can affect every source.
+    pub(crate) definitions: bool,
+}
+
 impl Hir {
     pub fn clear_references(&mut self) {
         let ref_symbols = self.symbols.iter_mut();
@@ -18,36 +34,167 @@ impl Hir {
                     d.references.clear();
                 }
                 SymbolKind::Ref(r) => r.target = None,
+                SymbolKind::Import(i) => i.target = None,
                 _ => {}
             }
         }
     }
 
     pub fn resolve_all(&mut self) {
+        self.pending = PendingChanges::default();
         self.resolve_references();
         self.resolve_types();
     }
 
+    /// Resolve only what might have been affected by changes since
+    /// the last resolution, the result is the same as with [`Hir::resolve_all`].
+    ///
+    /// Sources that were added and the sources of modules that import them
+    /// directly or indirectly are resolved again. Changes in definitions
+    /// can affect any source, so everything is resolved in that case.
+    pub fn resolve_changed(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+
+        if pending.definitions {
+            self.resolve_all();
+            return;
+        }
+
+        let sources = self.affected_sources(pending);
+
+        if sources.is_empty() {
+            return;
+        }
+
+        // Clearing only the stale references costs more
+        // than clearing all of them if every source is affected.
+        if sources.len() == self.sources.len() {
+            self.resolve_all();
+            return;
+        }
+
+        self.clear_references_in(&sources);
+        self.resolve_references_in(Some(&sources));
+        self.resolve_type_aliases(Some(&sources));
+        self.resolve_types_for_symbols(Some(&sources));
+    }
+
     pub fn resolve_references(&mut self) {
         self.clear_references();
+        self.resolve_references_in(None);
+    }
 
+    pub fn resolve_types(&mut self) {
+        self.resolve_type_aliases(None);
+        self.resolve_types_for_symbols(None);
+    }
+
+    fn resolve_references_in(&mut self, sources: Option<&HashSet<Source>>) {
         // The ordering is important here,
         // e.g. paths already rely on submodules
         // to be resolved.
-        self.resolve_imports();
-        self.resolve_paths();
-        self.resolve_scope_references();
+        self.resolve_imports(sources);
+        self.resolve_paths(sources);
+        self.resolve_scope_references(sources);
     }
 
-    pub fn resolve_types(&mut self) {
-        self.resolve_type_aliases();
-        self.resolve_types_for_all_symbols();
+    /// The changed sources along with the sources
+    /// that depend on them.
+    fn affected_sources(&self, pending: PendingChanges) -> HashSet<Source> {
+        let mut sources = pending
+            .sources
+            .into_iter()
+            .filter(|&source| self.sources.contains_key(source))
+            .collect::<HashSet<_>>();
+
+        // The sources that import each module.
+        let mut importers: HashMap<Module, Vec<Source>> = HashMap::new();
+
+        for data in self.symbols.values() {
+            let (Some(import), Some(source)) = (data.kind.as_import(), data.source.source) else {
+                continue;
+            };
+
+            // Imports that are not resolved might be resolved now,
+            // and new modules might change the targets of any import.
+            match import.target {
+                Some(target) if !pending.modules => {
+                    importers.entry(target).or_default().push(source);
+                }
+                _ => {
+                    sources.insert(source);
+                }
+            }
+        }
+
+        let mut modules = sources
+            .iter()
+            .map(|&source| self[source].module)
+            .collect::<HashSet<Module>>();
+
+        let mut queue = modules.iter().copied().collect::<VecDeque<_>>();
+
+        while let Some(module) = queue.pop_front() {
+            for &source in importers.get(&module).into_iter().flatten() {
+                if sources.insert(source) && modules.insert(self[source].module) {
+                    queue.push_back(self[source].module);
+                }
+            }
+        }
+
+        sources
     }
 
-    fn resolve_scope_references(&mut self) {
+    /// Clear the references of symbols in the given sources,
+    /// and the references to them from other sources.
+    fn clear_references_in(&mut self, sources: &HashSet<Source>) {
+        let stale_references = self
+            .symbols
+            .iter()
+            .filter_map(|(symbol, data)| {
+                let references = data.kind.references()?;
+
+                let stale = references
+                    .iter()
+                    .copied()
+                    .filter(|&r| {
+                        self.symbols
+                            .get(r)
+                            .is_none_or(|data| is_in_sources(Some(sources), data))
+                    })
+                    .collect::<Vec<_>>();
+
+                (!stale.is_empty()).then_some((symbol, stale))
+            })
+            .collect::<Vec<_>>();
+
+        for (symbol, stale) in stale_references {
+            if let Some(references) = self.symbol_mut(symbol).kind.references_mut() {
+                for r in stale {
+                    references.remove(&r);
+                }
+            }
+        }
+
+        for sym_data in self.symbols.values_mut() {
+            if !is_in_sources(Some(sources), sym_data) {
+                continue;
+            }
+
+            match &mut sym_data.kind {
+                SymbolKind::Decl(d) => d.target = None,
+                SymbolKind::Ref(r) => r.target = None,
+                SymbolKind::Import(i) => i.target = None,
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve_scope_references(&mut self, sources: Option<&HashSet<Source>>) {
         let ref_symbols_to_resolve: Vec<Symbol> = self
             .symbols
             .iter()
+            .filter(|(_, data)| is_in_sources(sources, data))
             .filter_map(|(s, data)| match &data.kind {
                 SymbolKind::Ref(ref_data) if !ref_data.part_of_path && !ref_data.field_access => {
                     Some(s)
@@ -101,10 +248,11 @@ impl Hir {
         }
     }
 
-    fn resolve_imports(&mut self) {
+    fn resolve_imports(&mut self, sources: Option<&HashSet<Source>>) {
         let import_symbols_to_resolve: Vec<Symbol> = self
             .symbols
             .iter()
+            .filter(|(_, data)| is_in_sources(sources, data))
             .filter_map(|(s, data)| match &data.kind {
                 SymbolKind::Import(_) => Some(s),
                 _ => None,
@@ -155,10 +303,11 @@ impl Hir {
         }
     }
 
-    fn resolve_paths(&mut self) {
+    fn resolve_paths(&mut self, sources: Option<&HashSet<Source>>) {
         let path_symbols_to_resolve: Vec<Vec<Symbol>> = self
             .symbols
             .iter()
+            .filter(|(_, data)| is_in_sources(sources, data))
             .filter_map(|(_, data)| match &data.kind {
                 SymbolKind::Path(p) => Some(p.segments.clone()),
                 _ => None,
@@ -219,6 +368,12 @@ impl Hir {
                                     _ => {}
                                 }
 
+                                if let Some(alias) =
+                                    self.symbol_mut(visible_symbol).kind.as_decl_mut()
+                                {
+                                    alias.references.insert(module_reference);
+                                }
+
                                 break;
                             }
                         }
@@ -256,6 +411,17 @@ impl Hir {
                 target_symbol = alias.target;
             }
 
+            // Sub-modules are referenced by their import alias.
+            if let Some(alias) = self[target_symbol]
+                .kind
+                .as_import()
+                .and_then(|import| import.alias)
+            {
+                if let Some(alias) = self.symbol_mut(alias).kind.as_decl_mut() {
+                    alias.references.insert(ref_symbol);
+                }
+            }
+
             let target_symbol_data = self.symbol_mut(target_symbol);
 
             match &mut target_symbol_data.kind {
@@ -274,3 +440,13 @@ impl Hir {
         }
     }
 }
+
+/// Whether the symbol is in one of the sources,
+/// all symbols are included without sources.
+fn is_in_sources(sources: Option<&HashSet<Source>>, data: &SymbolData) -> bool {
+    sources.is_none_or(|sources| {
+        data.source
+            .source
+            .is_some_and(|source| sources.contains(&source))
+    })
+}
@@ -1,24 +1,48 @@
+use super::is_in_sources;
 use crate::{
     eval::Value,
     hir::BuiltinTypes,
+    scope::ScopeParent,
+    source::{Source, SourceInfo},
     symbol::{ReferenceTarget, SymbolKind},
     ty::{Array, Function, Object, Type, TypeData},
-    HashSet, Hir, IndexMap, IndexSet, Symbol, TypeKind,
+    HashSet, Hir, IndexMap, IndexSet, Scope, Symbol, TypeKind,
 };
+use itertools::{Either, Itertools};
+use rhai_rowan::TextRange;
 use slotmap::SlotMap;
+use std::cmp::Reverse;
 
 impl Hir {
-    pub(crate) fn resolve_types_for_all_symbols(&mut self) {
-        let symbols = self.symbols.keys().collect::<Vec<_>>();
+    /// Resolve the types of all symbols in the given sources,
+    /// or of all symbols if no sources are given.
+    ///
+    /// Types of symbols in other sources are assumed to be resolved already.
+    pub(crate) fn resolve_types_for_symbols(&mut self, sources: Option<&HashSet<Source>>) {
+        let (symbols, resolved): (Vec<_>, Vec<_>) =
+            self.symbols.iter().partition_map(|(symbol, data)| {
+                if is_in_sources(sources, data) {
+                    Either::Left(symbol)
+                } else {
+                    Either::Right(symbol)
+                }
+            });
+
+        let mut seen = HashSet::with_capacity(self.symbols.len());
+        seen.extend(resolved);
 
-        let mut seen = HashSet::with_capacity(symbols.len());
         for symbol in symbols {
             self.resolve_type_for_symbol(&mut seen, symbol);
         }
     }
 
-    pub(crate) fn resolve_type_aliases(&mut self) {
-        let symbols = self.symbols.keys().collect::<Vec<_>>();
+    pub(crate) fn resolve_type_aliases(&mut self, sources: Option<&HashSet<Source>>) {
+        let symbols = self
+            .symbols
+            .iter()
+            .filter(|(_, data)| is_in_sources(sources, data))
+            .map(|(symbol, _)| symbol)
+            .collect::<Vec<_>>();
 
         let mut to_remove = HashSet::with_capacity(symbols.len());
 
@@ -145,8 +169,9 @@ impl Hir {
             SymbolKind::Ref(r) => match r.target {
                 Some(ReferenceTarget::Symbol(target_sym)) => {
                     self.resolve_type_for_symbol(seen, target_sym);
-                    let target_sym_data = self.symbols.get(target_sym).unwrap();
-                    self.symbols.get_mut(symbol).unwrap().ty = target_sym_data.ty;
+                    let target_ty = self.symbols.get(target_sym).unwrap().ty;
+                    let ty = self.narrowed_reference_type(symbol, target_sym, target_ty);
+                    self.symbols.get_mut(symbol).unwrap().ty = ty;
                 }
                 Some(ReferenceTarget::Module(_)) => {
                     sym_data.ty = self.builtin_types.module;
@@ -157,8 +182,14 @@ impl Hir {
                 let ty = if let Some(ty) = decl.ty_decl {
                     ty
                 } else if let Some(val) = decl.value {
+                    let references = decl.references.iter().copied().collect::<Vec<_>>();
+
                     self.resolve_type_for_symbol(seen, val);
-                    self.symbols.get(val).unwrap().ty
+                    let ty = self.symbols.get(val).unwrap().ty;
+
+                    // Assigned values might refer to the declaration itself.
+                    self.symbols.get_mut(symbol).unwrap().ty = ty;
+                    self.widened_collection_type(seen, symbol, ty, references)
                 } else {
                     self.builtin_types.unknown
                 };
@@ -180,7 +211,7 @@ impl Hir {
                 }
             }
             SymbolKind::Switch(switch) => {
-                let mut switch_types = IndexSet::default();
+                let mut switch_types = Vec::new();
                 let switch_arm_exprs = switch
                     .arms
                     .iter()
@@ -188,19 +219,9 @@ impl Hir {
                     .collect::<Vec<_>>();
                 for arm_expr in switch_arm_exprs {
                     self.resolve_type_for_symbol(seen, arm_expr);
-                    switch_types.insert(self.symbols.get(arm_expr).unwrap().ty);
+                    switch_types.push(self.symbols.get(arm_expr).unwrap().ty);
                 }
-                self.symbols.get_mut(symbol).unwrap().ty = if switch_types.is_empty() {
-                    self.builtin_types.void
-                } else if switch_types.len() == 1 {
-                    switch_types.pop().unwrap()
-                } else {
-                    self.types.insert(TypeData {
-                        source,
-                        kind: TypeKind::Union(switch_types),
-                        protected: false,
-                    })
-                };
+                self.symbols.get_mut(symbol).unwrap().ty = self.union_type(source, switch_types);
             }
             SymbolKind::If(if_sym) => {
                 let branch_symbols = if_sym
@@ -213,25 +234,15 @@ impl Hir {
                     self.resolve_type_for_symbol(seen, branch_sym);
                 }
 
-                let mut branch_types = branch_symbols
+                let branch_types = branch_symbols
                     .into_iter()
                     .map(|sym| match sym {
                         Some(last_branch_sym) => self.symbols.get(last_branch_sym).unwrap().ty,
                         None => self.builtin_types.void,
                     })
-                    .collect::<IndexSet<_>>();
+                    .collect::<Vec<_>>();
 
-                self.symbols.get_mut(symbol).unwrap().ty = if branch_types.is_empty() {
-                    self.builtin_types.void
-                } else if branch_types.len() == 1 {
-                    branch_types.pop().unwrap()
-                } else {
-                    self.types.insert(TypeData {
-                        source,
-                        kind: TypeKind::Union(branch_types),
-                        protected: false,
-                    })
-                };
+                self.symbols.get_mut(symbol).unwrap().ty = self.union_type(source, branch_types);
             }
             SymbolKind::Fn(f) => {
                 let scope = f.scope;
@@ -243,18 +254,19 @@ impl Hir {
                     f.ret_ty
                 };
 
-                let params = self
-                    .scopes
-                    .get(f.scope)
-                    .unwrap()
-                    .symbols
-                    .iter()
-                    .copied()
-                    .take_while(|&sym| self.symbols.get(sym).unwrap().is_param())
+                let params = f
+                    .params
+                    .clone()
+                    .into_iter()
                     .map(|sym| {
                         let sym_data = self.symbols.get(sym).unwrap();
                         let decl = sym_data.kind.as_decl().unwrap();
-                        (decl.name.clone(), sym)
+                        let name = if decl.is_rest {
+                            format!("...{}", decl.name)
+                        } else {
+                            decl.name.clone()
+                        };
+                        (name, sym)
                     })
                     .collect::<Vec<_>>();
 
@@ -269,19 +281,8 @@ impl Hir {
 
                 let ret = if is_def {
                     ret_ty
-                } else if let Some(last_expr) = self
-                    .scopes
-                    .get(scope)
-                    .unwrap()
-                    .symbols
-                    .iter()
-                    .copied()
-                    .find(|&sym| !self.symbols.get(sym).unwrap().is_param())
-                {
-                    self.resolve_type_for_symbol(seen, last_expr);
-                    self.symbols.get(last_expr).unwrap().ty
                 } else {
-                    self.builtin_types.unknown
+                    self.inferred_return_type(seen, symbol, scope)
                 };
 
                 self.symbols.get_mut(symbol).unwrap().ty = self.types.insert(TypeData {
@@ -321,20 +322,7 @@ impl Hir {
                     .map(|(name, sym)| (name, self.symbols.get(sym).unwrap().ty))
                     .collect::<Vec<_>>();
 
-                let ret = if let Some(last_expr) = self
-                    .scopes
-                    .get(scope)
-                    .unwrap()
-                    .symbols
-                    .iter()
-                    .copied()
-                    .find(|&sym| !self.symbols.get(sym).unwrap().is_param())
-                {
-                    self.resolve_type_for_symbol(seen, last_expr);
-                    self.symbols.get(last_expr).unwrap().ty
-                } else {
-                    self.builtin_types.unknown
-                };
+                let ret = self.inferred_return_type(seen, symbol, scope);
 
                 self.symbols.get_mut(symbol).unwrap().ty = self.types.insert(TypeData {
                     source,
@@ -347,7 +335,15 @@ impl Hir {
                 });
             }
             SymbolKind::Call(call) => {
+                let arguments = call.arguments.clone();
+
                 if let Some(lhs) = call.lhs {
+                    if let Some(reference) =
+                        self.call_reference(self[symbol].kind.as_call().unwrap())
+                    {
+                        self.resolve_call_overload(seen, lhs, reference, &arguments);
+                    }
+
                     self.resolve_type_for_symbol(seen, lhs);
                     let ty_data = self.types.get(self.symbols.get(lhs).unwrap().ty).unwrap();
 
@@ -362,11 +358,19 @@ impl Hir {
             }
             SymbolKind::Index(idx) => {
                 if let Some(base) = idx.base {
+                    let index = idx.index;
+
                     self.resolve_type_for_symbol(seen, base);
                     let ty_data = self.types.get(self.symbols.get(base).unwrap().ty).unwrap();
 
                     let ty = if let Some(arr) = ty_data.kind.as_array() {
                         arr.items
+                    } else if let Some(&field_ty) = ty_data.kind.as_object().and_then(|object| {
+                        object
+                            .fields
+                            .get(self[index?].value().as_string()?.as_str())
+                    }) {
+                        field_ty
                     } else {
                         self.builtin_types.unknown
                     };
@@ -381,22 +385,12 @@ impl Hir {
                     self.resolve_type_for_symbol(seen, *elem);
                 }
 
-                let mut types = elems
+                let types = elems
                     .into_iter()
                     .map(|sym| self.symbols.get(sym).unwrap().ty)
-                    .collect::<IndexSet<_>>();
+                    .collect::<Vec<_>>();
 
-                let items = if types.is_empty() {
-                    self.builtin_types.void
-                } else if types.len() == 1 {
-                    types.pop().unwrap()
-                } else {
-                    self.types.insert(TypeData {
-                        source,
-                        kind: TypeKind::Union(types),
-                        protected: false,
-                    })
-                };
+                let items = self.union_type(source, types);
 
                 let arr_ty = self.types.insert(TypeData {
                     source,
@@ -554,6 +548,412 @@ impl Hir {
             }
         }
     }
+
+    /// The union of the given types, nested unions are flattened.
+    ///
+    /// No types at all result in `()`.
+    fn union_type(&mut self, source: SourceInfo, types: impl IntoIterator<Item = Type>) -> Type {
+        let mut union = IndexSet::default();
+
+        for ty in types {
+            match &self[ty].kind {
+                TypeKind::Union(tys) => union.extend(tys.iter().copied()),
+                _ => {
+                    union.insert(ty);
+                }
+            }
+        }
+
+        if union.len() > 1 {
+            self.types.insert(TypeData {
+                source,
+                kind: TypeKind::Union(union),
+                protected: false,
+            })
+        } else {
+            union.pop().unwrap_or(self.builtin_types.void)
+        }
+    }
+
+    /// Infer the return type of a script function or closure
+    /// from all its `return` expressions and the last expression of its body.
+    ///
+    /// Unknown and never types are ignored if any other type is returned,
+    /// unknown types are most often caused by recursive calls.
+    fn inferred_return_type(
+        &mut self,
+        seen: &mut HashSet<Symbol>,
+        function: Symbol,
+        scope: Scope,
+    ) -> Type {
+        let mut returns = self
+            .descendant_symbols(scope)
+            .filter(|&sym| {
+                self[sym].kind.is_return() && self.containing_fn(sym, true) == Some(function)
+            })
+            .collect::<Vec<_>>();
+
+        // Keep the order of unions stable.
+        returns.sort_by_key(|&ret| self[ret].text_range().map(TextRange::start));
+
+        let mut returned = returns
+            .into_iter()
+            .map(|ret| self[ret].kind.as_return().unwrap().expr)
+            .collect::<Vec<_>>();
+
+        // Expressions of switch arms are also direct symbols of the scope,
+        // the tail is the outermost symbol that ends last.
+        let tail = self[scope].iter_symbols().max_by_key(|&sym| {
+            self[sym]
+                .text_range()
+                .map(|range| (range.end(), Reverse(range.start())))
+        });
+
+        match tail {
+            Some(tail) if self[tail].is_param() => returned.push(None),
+            Some(tail) => match self[tail].kind {
+                SymbolKind::Decl(_) | SymbolKind::Fn(_) => returned.push(None),
+                // Already part of the returned expressions.
+                SymbolKind::Return(_) => {}
+                _ => returned.push(Some(tail)),
+            },
+            None => returned.push(None),
+        }
+
+        let mut types = Vec::with_capacity(returned.len());
+
+        for expr in returned {
+            types.push(match expr {
+                Some(expr) => {
+                    self.resolve_type_for_symbol(seen, expr);
+                    self[expr].ty
+                }
+                None => self.builtin_types.void,
+            });
+        }
+
+        let known = types
+            .iter()
+            .copied()
+            .filter(|&ty| !self[ty].kind.is_unknown() && !self[ty].kind.is_never())
+            .collect::<Vec<_>>();
+
+        if !known.is_empty() {
+            self.union_type(self[function].source, known)
+        } else if types.iter().any(|&ty| self[ty].kind.is_unknown()) {
+            self.builtin_types.unknown
+        } else {
+            self.builtin_types.never
+        }
+    }
+
+    /// Narrow the type of a reference inside the branches
+    /// of `if type_of(x) == "..."` conditions.
+    fn narrowed_reference_type(&mut self, reference: Symbol, target: Symbol, ty: Type) -> Type {
+        let mut type_names = Vec::new();
+        let mut scope = self[reference].parent_scope;
+
+        while let Some(parent) = self.scope(scope).and_then(|data| data.parent) {
+            scope = match parent {
+                ScopeParent::Scope(parent) => parent,
+                ScopeParent::Symbol(parent) => {
+                    let type_name = self[parent]
+                        .kind
+                        .as_if()
+                        .and_then(|if_sym| {
+                            if_sym.branches.iter().find(|(_, branch)| *branch == scope)
+                        })
+                        .and_then(|(condition, _)| self.type_of_condition((*condition)?, target));
+
+                    if let Some(type_name) = type_name {
+                        type_names.push(type_name);
+                    }
+
+                    self[parent].parent_scope
+                }
+            };
+        }
+
+        let source = self[reference].source;
+
+        // Outer conditions first.
+        type_names.into_iter().rev().fold(ty, |ty, type_name| {
+            self.narrowed_type(source, ty, &type_name)
+        })
+    }
+
+    /// The type name compared in a `type_of(x) == "..."` condition
+    /// where `x` refers to the given target.
+    fn type_of_condition(&self, condition: Symbol, target: Symbol) -> Option<String> {
+        let binary = self[condition].kind.as_binary()?;
+
+        if binary.lookup_text != "==" {
+            return None;
+        }
+
+        let (lhs, rhs) = (binary.lhs?, binary.rhs?);
+        let (call, lit) = if self[lhs].kind.is_call() {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+
+        let call = self[call].kind.as_call()?;
+
+        if self[call.lhs?].kind.as_reference()?.name != "type_of" {
+            return None;
+        }
+
+        let &[arg] = call.arguments.as_slice() else {
+            return None;
+        };
+
+        match self[arg].kind.as_reference()?.target {
+            Some(ReferenceTarget::Symbol(arg_target)) if arg_target == target => {}
+            _ => return None,
+        }
+
+        self[lit].value().as_string().cloned()
+    }
+
+    /// Narrow the type to the given name returned by `type_of`.
+    ///
+    /// Matching members of unions are kept, otherwise
+    /// the type is replaced by the named type.
+    fn narrowed_type(&mut self, source: SourceInfo, ty: Type, type_name: &str) -> Type {
+        let (kind, builtin) = match type_name {
+            "i64" | "i32" => (TypeKind::Int, Some(self.builtin_types.int)),
+            "f64" | "f32" => (TypeKind::Float, Some(self.builtin_types.float)),
+            "bool" => (TypeKind::Bool, Some(self.builtin_types.bool)),
+            "char" => (TypeKind::Char, Some(self.builtin_types.char)),
+            "string" => (TypeKind::String, Some(self.builtin_types.string)),
+            "timestamp" => (TypeKind::Timestamp, Some(self.builtin_types.timestamp)),
+            "()" => (TypeKind::Void, Some(self.builtin_types.void)),
+            "array" => (
+                TypeKind::Array(Array {
+                    items: self.builtin_types.unknown,
+                }),
+                None,
+            ),
+            "map" => (
+                TypeKind::Object(Object {
+                    fields: IndexMap::default(),
+                }),
+                None,
+            ),
+            _ => return ty,
+        };
+
+        let discriminant = core::mem::discriminant(&kind);
+
+        let matching = match &self[ty].kind {
+            TypeKind::Union(tys) => tys
+                .iter()
+                .copied()
+                .filter(|&ty| core::mem::discriminant(&self[ty].kind) == discriminant)
+                .collect::<Vec<_>>(),
+            ty_kind if core::mem::discriminant(ty_kind) == discriminant => return ty,
+            _ => Vec::new(),
+        };
+
+        if !matching.is_empty() {
+            return self.union_type(source, matching);
+        }
+
+        builtin.unwrap_or_else(|| {
+            self.types.insert(TypeData {
+                source,
+                kind,
+                protected: false,
+            })
+        })
+    }
+
+    /// Widen the array item or object field types of a declaration
+    /// with the values assigned via its references,
+    /// e.g. `arr[0] = value`, `map.field = value` or `map["field"] = value`.
+    fn widened_collection_type(
+        &mut self,
+        seen: &mut HashSet<Symbol>,
+        decl: Symbol,
+        ty: Type,
+        mut references: Vec<Symbol>,
+    ) -> Type {
+        if !matches!(self[ty].kind, TypeKind::Array(_) | TypeKind::Object(_)) {
+            return ty;
+        }
+
+        // Keep the order of unions stable.
+        references.sort_by_key(|&reference| self[reference].text_range().map(TextRange::start));
+
+        let assignments = references
+            .into_iter()
+            .filter_map(|reference| self.collection_assignment(reference))
+            .collect::<Vec<_>>();
+
+        if assignments.is_empty() {
+            return ty;
+        }
+
+        let mut assigned = Vec::with_capacity(assignments.len());
+
+        for (key, value) in assignments {
+            self.resolve_type_for_symbol(seen, value);
+            assigned.push((key, self[value].ty));
+        }
+
+        let source = self[decl].source;
+
+        let kind = match self[ty].kind.clone() {
+            TypeKind::Array(arr) => {
+                let items = (!self[arr.items].kind.is_void())
+                    .then_some(arr.items)
+                    .into_iter()
+                    .chain(
+                        assigned
+                            .into_iter()
+                            .filter(|(key, _)| key.is_none())
+                            .map(|(_, ty)| ty),
+                    )
+                    .collect::<Vec<_>>();
+
+                if items.is_empty() {
+                    return ty;
+                }
+
+                TypeKind::Array(Array {
+                    items: self.union_type(source, items),
+                })
+            }
+            TypeKind::Object(mut object) => {
+                for (key, value_ty) in assigned {
+                    let Some(key) = key else {
+                        continue;
+                    };
+
+                    let field_ty = match object.fields.get(&key) {
+                        Some(&field_ty) => self.union_type(source, [field_ty, value_ty]),
+                        None => value_ty,
+                    };
+
+                    object.fields.insert(key, field_ty);
+                }
+
+                TypeKind::Object(object)
+            }
+            _ => return ty,
+        };
+
+        self.types.insert(TypeData {
+            source,
+            kind,
+            protected: false,
+        })
+    }
+
+    /// The key and value of an assignment to an item or field
+    /// of the referenced value, the key is `None` for non-string indices.
+    fn collection_assignment(&self, reference: Symbol) -> Option<(Option<String>, Symbol)> {
+        let parent = self.parent_symbol(reference)?;
+        let binary = self[parent].kind.as_binary()?;
+
+        if binary.lookup_text == "=" {
+            let index = self[binary.lhs?].kind.as_index()?;
+
+            if index.base != Some(reference) {
+                return None;
+            }
+
+            let key = index
+                .index
+                .and_then(|index| self[index].value().as_string().cloned());
+
+            return Some((key, binary.rhs?));
+        }
+
+        if !binary.is_field_access() || binary.lhs != Some(reference) {
+            return None;
+        }
+
+        let field = self[binary.rhs?].name(self)?.to_string();
+
+        let assignment = self[self.parent_symbol(parent)?].kind.as_binary()?;
+
+        if assignment.lookup_text != "=" || assignment.lhs != Some(parent) {
+            return None;
+        }
+
+        Some((Some(field), assignment.rhs?))
+    }
+
+    /// The symbol that owns the scope of the given symbol.
+    fn parent_symbol(&self, symbol: Symbol) -> Option<Symbol> {
+        self.scope(self[symbol].parent_scope)?
+            .parent?
+            .as_symbol()
+            .copied()
+    }
+
+    /// Point the reference of a call to the overload
+    /// of the function that matches the arguments best.
+    ///
+    /// References are resolved to the first visible function
+    /// by name only, this can only be corrected once
+    /// the types of the arguments are known.
+    fn resolve_call_overload(
+        &mut self,
+        seen: &mut HashSet<Symbol>,
+        lhs: Symbol,
+        reference: Symbol,
+        arguments: &[Symbol],
+    ) {
+        let Some(ReferenceTarget::Symbol(current)) = self[reference].target() else {
+            return;
+        };
+
+        if !self[current].kind.is_fn() {
+            return;
+        }
+
+        for &arg in arguments {
+            self.resolve_type_for_symbol(seen, arg);
+        }
+
+        let arg_types = arguments
+            .iter()
+            .map(|&arg| self[arg].ty)
+            .collect::<Vec<_>>();
+
+        // Calls that no overload accepts are attributed
+        // to the first declaration for consistent errors.
+        let Some(overload) = self
+            .select_overload(current, &arg_types)
+            .or_else(|| self.overloads(current).next())
+        else {
+            return;
+        };
+
+        if overload == current {
+            return;
+        }
+
+        if let Some(f) = self.symbol_mut(current).kind.as_fn_mut() {
+            f.references.remove(&reference);
+        }
+
+        if let Some(f) = self.symbol_mut(overload).kind.as_fn_mut() {
+            f.references.insert(reference);
+        }
+
+        if let Some(r) = self.symbol_mut(reference).kind.as_reference_mut() {
+            r.target = Some(ReferenceTarget::Symbol(overload));
+        }
+
+        // The types might have been resolved
+        // with the previous target already.
+        seen.remove(&reference);
+        seen.remove(&lhs);
+    }
 }
 
 fn resolve_and_replace(
@@ -17,6 +17,7 @@ pub mod hir;
 pub mod module;
 pub mod scope;
 pub mod source;
+pub mod suppression;
 pub mod symbol;
 pub mod ty;
 pub(crate) mod util;
@@ -1,11 +1,12 @@
 use std::{ffi::OsStr, path::Path};
 
-use crate::{source::Source, Hir, IndexSet, Scope};
+use crate::{source::Source, util::script_url, HashMap, Hir, IndexSet, Scope, Symbol};
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 slotmap::new_key_type! { pub struct Module; }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ModuleKind {
     /// The static module is the root of every Rhai script,
     /// items and modules defined in the scope of the static module
@@ -31,7 +32,7 @@ impl core::fmt::Display for ModuleKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleData {
     pub scope: Scope,
     pub kind: ModuleKind,
@@ -52,6 +53,17 @@ impl ModuleData {
     }
 }
 
+/// A resolved import of a module in another module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleImport {
+    /// The module that contains the import statement.
+    pub from: Module,
+    /// The imported module.
+    pub to: Module,
+    /// The import symbol.
+    pub import: Symbol,
+}
+
 pub const STATIC_URL_SCHEME: &str = "rhai-static";
 
 /// Used to resolve module URLs for import statements and definitions.
@@ -118,3 +130,121 @@ impl ModuleResolver for DefaultModuleResolver {
         }
     }
 }
+
+/// A resolver similar to the file and static module
+/// resolvers of Rhai that are typically used by hosts.
+///
+/// An import path is resolved in the following order:
+///
+/// - modules registered by name, the name must match the import path exactly,
+/// - absolute URLs,
+/// - each of the base URLs, unless the path starts with `./` or `../`,
+/// - relative to the importing module.
+///
+/// Modules that already exist in the HIR are preferred,
+/// otherwise the first candidate URL is used.
+#[derive(Debug, Clone)]
+pub struct ConfiguredModuleResolver {
+    base_urls: Vec<Url>,
+    extension: String,
+    named_modules: HashMap<String, Url>,
+}
+
+impl Default for ConfiguredModuleResolver {
+    fn default() -> Self {
+        Self {
+            base_urls: Vec::new(),
+            extension: String::from("rhai"),
+            named_modules: HashMap::default(),
+        }
+    }
+}
+
+impl ConfiguredModuleResolver {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a base URL that is searched for imported modules.
+    ///
+    /// The URL should end with `/` in order to be
+    /// treated as a directory.
+    #[must_use]
+    pub fn with_base_url(mut self, url: Url) -> Self {
+        self.base_urls.push(url);
+        self
+    }
+
+    /// Set the extension that is added to import paths without it.
+    #[must_use]
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.extension = extension.into();
+        self
+    }
+
+    /// Register a module that is imported by the given name,
+    /// e.g. a module of a static module resolver.
+    ///
+    /// Definition URLs (`.d.rhai`) are replaced with
+    /// the URL of the module they define.
+    #[must_use]
+    pub fn with_named_module(mut self, name: impl Into<String>, url: Url) -> Self {
+        let url = script_url(&url).unwrap_or(url);
+        self.named_modules.insert(name.into(), url);
+        self
+    }
+
+    fn candidate_urls(&self, from: &Url, path: &str) -> anyhow::Result<Vec<Url>> {
+        if let Some(url) = self.named_modules.get(path) {
+            return Ok(vec![url.clone()]);
+        }
+
+        if let Ok(url) = Url::parse(path) {
+            return Ok(vec![url]);
+        }
+
+        let path = if Path::new(path).extension() == Some(OsStr::new(&self.extension)) {
+            path.to_string()
+        } else {
+            format!("{path}.{}", self.extension)
+        };
+
+        let mut urls = Vec::with_capacity(self.base_urls.len() + 1);
+
+        if !path.starts_with("./") && !path.starts_with("../") {
+            for base in &self.base_urls {
+                urls.push(base.join(&path)?);
+            }
+        }
+
+        urls.push(from.join(&path)?);
+
+        Ok(urls)
+    }
+}
+
+impl ModuleResolver for ConfiguredModuleResolver {
+    fn resolve_url(&self, from: &Url, path: &str) -> anyhow::Result<Url> {
+        self.candidate_urls(from, path)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("could not resolve import path"))
+    }
+
+    fn resolve_url_from_module(&self, hir: &Hir, from: Module, path: &str) -> anyhow::Result<Url> {
+        let from = hir[from]
+            .url()
+            .ok_or_else(|| anyhow::anyhow!("could not determine base url"))?;
+
+        let mut urls = self.candidate_urls(from, path)?;
+
+        match urls.iter().position(|url| hir.module_by_url(url).is_some()) {
+            Some(idx) => Ok(urls.swap_remove(idx)),
+            None => urls
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("could not resolve import path")),
+        }
+    }
+}
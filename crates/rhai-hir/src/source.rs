@@ -1,19 +1,22 @@
 use rhai_rowan::TextRange;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::Module;
+use crate::{suppression::Suppression, Module};
 
 slotmap::new_key_type! { pub struct Source; }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct SourceData {
     pub url: Url,
     pub kind: SourceKind,
     pub module: Module,
+    /// Lint suppression comments in the source.
+    pub suppressions: Vec<Suppression>,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum SourceKind {
     Script,
     Def,
@@ -37,7 +40,7 @@ impl SourceKind {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct SourceInfo {
     pub source: Option<Source>,
     pub text_range: Option<TextRange>,
@@ -0,0 +1,121 @@
+//! Lint suppression comments.
+//!
+//! A `// rhai-lsp: allow(code, ...)` comment suppresses errors
+//! on the same line if it follows code, otherwise on the next line of code.
+//!
+//! A `//! rhai-lsp: allow(code, ...)` comment suppresses errors
+//! in the entire file.
+
+use rhai_rowan::{
+    syntax::{SyntaxElement, SyntaxKind::COMMENT_LINE, SyntaxNode},
+    TextRange, TextSize,
+};
+use serde::{Deserialize, Serialize};
+
+const PREFIX: &str = "rhai-lsp:";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suppression {
+    /// The error codes that are allowed.
+    pub codes: Vec<String>,
+    /// The range of the comment.
+    pub text_range: TextRange,
+    /// The range where errors are suppressed,
+    /// `None` for the entire file.
+    pub target: Option<TextRange>,
+}
+
+impl Suppression {
+    /// Whether an error at the given range is suppressed
+    /// if it has one of the codes.
+    #[must_use]
+    pub fn covers(&self, range: TextRange) -> bool {
+        self.target
+            .is_none_or(|target| target.contains_inclusive(range.start()))
+    }
+}
+
+pub(crate) fn collect_suppressions(syntax: &SyntaxNode) -> Vec<Suppression> {
+    let text = syntax.text().to_string();
+
+    syntax
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| token.kind() == COMMENT_LINE)
+        .filter_map(|token| {
+            let (file_level, comment) = match token.text().strip_prefix("//!") {
+                Some(comment) => (true, comment),
+                None => (false, token.text().strip_prefix("//")?),
+            };
+
+            let codes = parse_allow(comment)?;
+            let text_range = token.text_range();
+
+            let target = if file_level {
+                None
+            } else {
+                Some(target_range(&text, text_range))
+            };
+
+            Some(Suppression {
+                codes,
+                text_range,
+                target,
+            })
+        })
+        .collect()
+}
+
+/// Parse the codes from `rhai-lsp: allow(code, ...)`.
+fn parse_allow(comment: &str) -> Option<Vec<String>> {
+    let codes = comment
+        .trim()
+        .strip_prefix(PREFIX)?
+        .trim_start()
+        .strip_prefix("allow(")?
+        .trim_end()
+        .strip_suffix(')')?;
+
+    Some(
+        codes
+            .split(',')
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// The code before a trailing comment on the same line,
+/// or the next line that is not empty or a comment.
+fn target_range(text: &str, comment_range: TextRange) -> TextRange {
+    let comment_start = usize::from(comment_range.start());
+    let line_start = text[..comment_start].rfind('\n').map_or(0, |idx| idx + 1);
+
+    if !text[line_start..comment_start].trim().is_empty() {
+        return range(line_start, comment_start);
+    }
+
+    let mut line_start = usize::from(comment_range.end());
+
+    while let Some(newline) = text[line_start..].find('\n') {
+        line_start += newline + 1;
+
+        let line_end = text[line_start..]
+            .find('\n')
+            .map_or(text.len(), |idx| line_start + idx);
+
+        let line = text[line_start..line_end].trim();
+
+        if !line.is_empty() && !line.starts_with("//") {
+            return range(line_start, line_end);
+        }
+    }
+
+    TextRange::empty(comment_range.end())
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn range(start: usize, end: usize) -> TextRange {
+    TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32))
+}
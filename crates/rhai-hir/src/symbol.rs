@@ -1,11 +1,12 @@
 use super::module::Module;
 use crate::{eval::Value, source::SourceInfo, ty::Type, HashSet, Hir, IndexMap, Scope};
 use rhai_rowan::{syntax::SyntaxKind, TextRange};
+use serde::{Deserialize, Serialize};
 use strum::IntoStaticStr;
 
 slotmap::new_key_type! { pub struct Symbol; }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct SymbolData {
     pub source: SourceInfo,
@@ -97,7 +98,7 @@ impl SymbolData {
     }
 }
 
-#[derive(Debug, Clone, IntoStaticStr)]
+#[derive(Debug, Clone, IntoStaticStr, Serialize, Deserialize)]
 pub enum SymbolKind {
     Block(BlockSymbol),
     Fn(FnSymbol),
@@ -131,6 +132,26 @@ pub enum SymbolKind {
 }
 
 impl SymbolKind {
+    /// The symbols that reference this symbol,
+    /// only functions and declarations are referenced.
+    #[must_use]
+    pub fn references(&self) -> Option<&HashSet<Symbol>> {
+        match self {
+            Self::Fn(f) => Some(&f.references),
+            Self::Decl(d) => Some(&d.references),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn references_mut(&mut self) -> Option<&mut HashSet<Symbol>> {
+        match self {
+            Self::Fn(f) => Some(&mut f.references),
+            Self::Decl(d) => Some(&mut d.references),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the symbol kind is [`Block`].
     ///
     /// [`Block`]: SymbolKind::Block
@@ -165,6 +186,15 @@ impl SymbolKind {
         }
     }
 
+    #[must_use]
+    pub fn as_fn_mut(&mut self) -> Option<&mut FnSymbol> {
+        if let Self::Fn(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
     /// Returns `true` if the symbol kind is [`Op`].
     ///
     /// [`Op`]: SymbolKind::Op
@@ -670,16 +700,19 @@ impl SymbolKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockSymbol {
     pub scope: Scope,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct FnSymbol {
     pub name: String,
     pub docs: String,
     pub scope: Scope,
+    /// The parameters of the function in order,
+    /// they are also part of the function scope.
+    pub params: Vec<Symbol>,
     pub references: HashSet<Symbol>,
     pub getter: bool,
     pub setter: bool,
@@ -687,7 +720,49 @@ pub struct FnSymbol {
     pub ret_ty: Type,
 }
 
-#[derive(Debug, Default, Clone)]
+impl FnSymbol {
+    /// Whether the last parameter is a `...rest` parameter.
+    #[must_use]
+    pub fn is_variadic(&self, hir: &Hir) -> bool {
+        self.params
+            .last()
+            .and_then(|&p| hir[p].kind.as_decl())
+            .is_some_and(|d| d.is_rest)
+    }
+
+    /// The amount of arguments that must be always provided.
+    #[must_use]
+    pub fn required_param_count(&self, hir: &Hir) -> usize {
+        if self.is_variadic(hir) {
+            self.params.len() - 1
+        } else {
+            self.params.len()
+        }
+    }
+
+    /// Whether the function can be called with the given amount of arguments.
+    #[must_use]
+    pub fn accepts_arg_count(&self, hir: &Hir, arg_count: usize) -> bool {
+        let required = self.required_param_count(hir);
+
+        if self.is_variadic(hir) {
+            arg_count >= required
+        } else {
+            arg_count == required
+        }
+    }
+
+    /// The parameter that receives the argument at the given position.
+    #[must_use]
+    pub fn param_for_arg(&self, hir: &Hir, arg_idx: usize) -> Option<Symbol> {
+        self.params
+            .get(arg_idx)
+            .or_else(|| self.params.last().filter(|_| self.is_variadic(hir)))
+            .copied()
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct OpSymbol {
     pub name: String,
     pub docs: String,
@@ -717,11 +792,14 @@ impl OpSymbol {
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DeclSymbol {
     pub name: String,
     pub docs: String,
     pub is_param: bool,
+    /// Whether this is a `...rest` parameter
+    /// of a function definition.
+    pub is_rest: bool,
     pub is_const: bool,
     pub is_pat: bool,
     pub is_import: bool,
@@ -735,7 +813,7 @@ pub struct DeclSymbol {
     pub target: Option<ReferenceTarget>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ReferenceSymbol {
     pub target: Option<ReferenceTarget>,
     pub part_of_path: bool,
@@ -743,26 +821,26 @@ pub struct ReferenceSymbol {
     pub name: String,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct PathSymbol {
     pub scope: Scope,
     pub segments: Vec<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct LitSymbol {
     pub value: Value,
     pub interpolated_scopes: Vec<Scope>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnarySymbol {
     pub lookup_text: String,
     pub op: Option<SyntaxKind>,
     pub rhs: Option<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BinarySymbol {
     pub scope: Scope,
     pub lookup_text: String,
@@ -778,7 +856,7 @@ impl BinarySymbol {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BinaryOpKind {
     Regular(SyntaxKind),
     Custom(CustomBinaryOp),
@@ -820,35 +898,35 @@ impl BinaryOpKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomBinaryOp {
     pub name: String,
     pub range: TextRange,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArraySymbol {
     pub values: Vec<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexSymbol {
     pub base: Option<Symbol>,
     pub index: Option<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallSymbol {
     pub lhs: Option<Symbol>,
     pub arguments: Vec<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObjectSymbol {
     pub fields: IndexMap<String, ObjectField>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObjectField {
     pub property_syntax: SourceInfo,
     pub property_name: String,
@@ -856,72 +934,72 @@ pub struct ObjectField {
     pub value: Option<Symbol>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThrowSymbol {
     pub expr: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ClosureSymbol {
     pub scope: Scope,
     pub expr: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct IfSymbol {
     /// Conditions and scopes for each branch.
     pub branches: Vec<(Option<Symbol>, Scope)>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct LoopSymbol {
     pub scope: Scope,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ForSymbol {
     pub cursor: Option<Symbol>,
     pub scope: Scope,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct WhileSymbol {
     pub condition: Option<Symbol>,
     pub scope: Scope,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BreakSymbol {
     pub expr: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ContinueSymbol {}
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ReturnSymbol {
     pub expr: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SwitchSymbol {
     pub target: Option<Symbol>,
     pub arms: Vec<SwitchArm>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SwitchArm {
     pub pat_expr: Option<Symbol>,
     pub condition_expr: Option<Symbol>,
     pub value_expr: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ExportSymbol {
     pub target: Option<Symbol>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ImportSymbol {
     pub scope: Scope,
     pub expr: Option<Symbol>,
@@ -937,16 +1015,16 @@ impl ImportSymbol {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TrySymbol {
     pub try_scope: Scope,
     pub catch_scope: Scope,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DiscardSymbol {}
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ReferenceTarget {
     Symbol(Symbol),
     Module(Module),
@@ -990,7 +1068,7 @@ impl ReferenceTarget {
 
 /// A symbol that does not and cannot originate
 /// from source code and was injected into the hir.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VirtualSymbol {
     Proxy(VirtualProxySymbol),
     Module(VirtualModuleSymbol),
@@ -1052,14 +1130,14 @@ impl VirtualSymbol {
 }
 
 /// A symbol that points to some other symbols transparently.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct VirtualProxySymbol {
     pub target: Symbol,
 }
 
 /// A symbol that is used to inject modules into scopes
 /// without an import statement.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualModuleSymbol {
     pub name: String,
     pub module: Module,
@@ -1068,13 +1146,13 @@ pub struct VirtualModuleSymbol {
 /// A symbol that points to some other symbol under an alias.
 /// 
 /// Has the same semantics as [`VirtualModuleSymbol`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualAliasSymbol {
     pub name: String,
     pub target: Symbol,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeDeclSymbol {
     pub docs: String,
     pub ty: Type,
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 use crate::{source::SourceInfo, Hir, IndexMap, IndexSet};
 use core::fmt;
+use serde::{Deserialize, Serialize};
 
 slotmap::new_key_type! { pub struct Type; }
 
@@ -56,9 +57,51 @@ impl Type {
             _ => false,
         }
     }
+
+    /// Whether a value of this type can be used where
+    /// a value of the `target` type is expected.
+    ///
+    /// This is a lenient check, unknown or unresolved types
+    /// are compatible with any other type.
+    #[must_use]
+    pub fn is_assignable_to(self, hir: &Hir, target: Type) -> bool {
+        if self.is(hir, target, false) {
+            return true;
+        }
+
+        match (&hir[self].kind, &hir[target].kind) {
+            (TypeKind::Alias(_, ty), _) => ty.is_assignable_to(hir, target),
+            (_, TypeKind::Alias(_, ty)) => self.is_assignable_to(hir, *ty),
+            (TypeKind::Unknown | TypeKind::Unresolved(_) | TypeKind::Never, _)
+            | (_, TypeKind::Unknown | TypeKind::Unresolved(_))
+            | (TypeKind::Fn(_), TypeKind::Fn(_)) => true,
+            (TypeKind::Union(tys), _) => tys.iter().all(|ty| ty.is_assignable_to(hir, target)),
+            (_, TypeKind::Union(tys)) => tys.iter().any(|ty| self.is_assignable_to(hir, *ty)),
+            (TypeKind::Array(arr1), TypeKind::Array(arr2)) => {
+                // Empty arrays have void items.
+                hir[arr1.items].kind.is_void() || arr1.items.is_assignable_to(hir, arr2.items)
+            }
+            (TypeKind::Object(obj1), TypeKind::Object(obj2)) => {
+                obj1.fields.iter().all(|(name, ty)| {
+                    obj2.fields
+                        .get(name)
+                        .is_none_or(|ty2| ty.is_assignable_to(hir, *ty2))
+                })
+            }
+            (TypeKind::Tuple(t1), TypeKind::Tuple(t2)) => {
+                t1.len() == t2.len()
+                    && t1
+                        .iter()
+                        .zip(t2.iter())
+                        .all(|(t1, t2)| t1.is_assignable_to(hir, *t2))
+            }
+            (TypeKind::Primitive(p1), TypeKind::Primitive(p2)) => p1 == p2,
+            (kind1, kind2) => core::mem::discriminant(kind1) == core::mem::discriminant(kind2),
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct TypeData {
     pub source: SourceInfo,
@@ -165,7 +208,7 @@ impl core::fmt::Display for TypeFormatter<'_> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypeKind {
     Module,
     Int,
@@ -377,17 +420,17 @@ impl Default for TypeKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Object {
     pub fields: IndexMap<String, Type>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Array {
     pub items: Type,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     pub is_closure: bool,
     pub params: Vec<(String, Type)>,
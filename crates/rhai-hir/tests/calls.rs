@@ -0,0 +1,67 @@
+use rhai_hir::{symbol::SymbolKind, Hir, Symbol};
+use rhai_rowan::parser::Parser;
+
+fn find_fn(hir: &Hir, name: &str) -> Symbol {
+    hir.symbols()
+        .find(|(_, data)| matches!(&data.kind, SymbolKind::Fn(f) if f.name == name))
+        .map(|(symbol, _)| symbol)
+        .unwrap()
+}
+
+#[test]
+fn test_calls_across_imports() {
+    let root_src = r#"
+fn main() {
+    import "./module.rhai" as m;
+
+    let f = || m::helper();
+    m::helper();
+}
+
+main();
+"#;
+
+    let module_src = r#"
+fn helper() {}
+"#;
+
+    let mut hir = Hir::new();
+
+    hir.add_source(
+        &"test:///root.rhai".parse().unwrap(),
+        &Parser::new(root_src).parse_script().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///module.rhai".parse().unwrap(),
+        &Parser::new(module_src).parse_script().into_syntax(),
+    );
+
+    hir.resolve_all();
+
+    let main_fn = find_fn(&hir, "main");
+    let helper_fn = find_fn(&hir, "helper");
+
+    let helper_references = &hir[helper_fn].kind.as_fn().unwrap().references;
+    assert_eq!(helper_references.len(), 2);
+
+    for &reference in helper_references {
+        assert_eq!(hir.containing_fn(reference, false), Some(main_fn));
+    }
+
+    let main_references = &hir[main_fn].kind.as_fn().unwrap().references;
+    assert_eq!(main_references.len(), 1);
+
+    for &reference in main_references {
+        assert_eq!(hir.containing_fn(reference, false), None);
+    }
+
+    let call_targets = hir
+        .descendant_symbols(hir[main_fn].kind.as_fn().unwrap().scope)
+        .filter_map(|symbol| match &hir[symbol].kind {
+            SymbolKind::Call(call) => hir.call_target(call),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(call_targets, [helper_fn, helper_fn]);
+}
@@ -38,7 +38,7 @@ print("hello");
     let global_src = r#"
 module static;
 
-fn print();
+fn print(data: ?);
 "#;
 
     let mut hir = Hir::new();
@@ -66,7 +66,7 @@ print("hello");
     let global_src = r#"
 module;
 
-fn print();
+fn print(data: ?);
 "#;
 
     let mut hir = Hir::new();
@@ -94,7 +94,7 @@ print("hello");
     let global_src = r#"
 module "./root.rhai";
 
-fn print();
+fn print(data: ?);
 "#;
 
     let mut hir = Hir::new();
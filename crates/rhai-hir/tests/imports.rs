@@ -1,4 +1,4 @@
-use rhai_hir::Hir;
+use rhai_hir::{error::ErrorKind, module::ConfiguredModuleResolver, Hir};
 use rhai_rowan::parser::Parser;
 
 #[test]
@@ -83,3 +83,132 @@ import "./foo.rhai" as foo;
 
     assert_eq!(hir.missing_modules().len(), 1);
 }
+
+#[test]
+fn test_configured_module_resolver() {
+    let root_src = r#"
+import "crypto" as crypto;
+import "utils/math" as math;
+import "./local" as local;
+
+crypto::hash("");
+math::add;
+local::x;
+"#;
+
+    let crypto_src = r#"
+module;
+
+fn hash(data: String) -> String;
+"#;
+
+    let math_src = r#"
+export const add = 1;
+"#;
+
+    let local_src = r#"
+export const x = 1;
+"#;
+
+    let mut hir = Hir::new();
+
+    hir.set_import_resolver(
+        ConfiguredModuleResolver::new()
+            .with_base_url("test:///lib/".parse().unwrap())
+            .with_base_url("test:///scripts/".parse().unwrap())
+            .with_named_module("crypto", "test:///defs/crypto.d.rhai".parse().unwrap()),
+    );
+
+    hir.add_source(
+        &"test:///app/main.rhai".parse().unwrap(),
+        &Parser::new(root_src).parse_script().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///defs/crypto.d.rhai".parse().unwrap(),
+        &Parser::new(crypto_src).parse_def().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///scripts/utils/math.rhai".parse().unwrap(),
+        &Parser::new(math_src).parse_script().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///app/local.rhai".parse().unwrap(),
+        &Parser::new(local_src).parse_script().into_syntax(),
+    );
+
+    hir.resolve_all();
+
+    assert_eq!(hir.missing_modules().len(), 0);
+    assert!(hir.errors().is_empty());
+}
+
+#[test]
+fn test_circular_imports() {
+    let a_src = r#"
+import "./b.rhai" as b;
+"#;
+
+    let b_src = r#"
+import "./c.rhai" as c;
+"#;
+
+    let c_src = r#"
+import "./a.rhai" as a;
+"#;
+
+    let d_src = r#"
+import "./a.rhai" as a;
+"#;
+
+    let mut hir = Hir::new();
+
+    for (url, src) in [
+        ("test:///a.rhai", a_src),
+        ("test:///b.rhai", b_src),
+        ("test:///c.rhai", c_src),
+        ("test:///d.rhai", d_src),
+    ] {
+        hir.add_source(
+            &url.parse().unwrap(),
+            &Parser::new(src).parse_script().into_syntax(),
+        );
+    }
+
+    hir.resolve_all();
+
+    assert_eq!(hir.module_imports().len(), 4);
+
+    let a = hir.source_of(&"test:///a.rhai".parse().unwrap()).unwrap();
+    let a_module = hir.module_by_source(a).unwrap();
+
+    let import = hir
+        .module_imports()
+        .into_iter()
+        .find(|i| i.from == a_module)
+        .unwrap();
+
+    let cycles = hir.import_cycles();
+    assert_eq!(cycles.len(), 3);
+
+    let cycle = cycles
+        .iter()
+        .find(|c| c[0].import == import.import)
+        .unwrap();
+    assert_eq!(cycle.len(), 3);
+    assert_eq!(cycle[0], import);
+    assert_eq!(cycle[2].to, a_module);
+
+    let errors = hir.errors();
+
+    let circular = errors
+        .iter()
+        .filter_map(|e| match &e.kind {
+            ErrorKind::CircularImport { import, cycle } => Some((*import, cycle.len())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // The import in `d.rhai` is not part of the cycle.
+    assert_eq!(circular.len(), 3);
+    assert!(circular.iter().all(|&(_, len)| len == 2));
+}
@@ -0,0 +1,219 @@
+use rhai_hir::Hir;
+use rhai_rowan::parser::Parser;
+
+const STATIC_SRC: &str = r#"
+module static;
+
+fn print(value: ?);
+"#;
+
+const A_SRC: &str = r#"
+import "./b.rhai" as b;
+
+export const x = b::foo();
+print(x);
+"#;
+
+const B_SRC: &str = r#"
+fn foo() {
+    1
+}
+"#;
+
+const C_SRC: &str = r#"
+let y = 2;
+print(y + undefined);
+"#;
+
+/// Applies every change to a HIR that is resolved incrementally
+/// and to one that is resolved entirely.
+struct Hirs {
+    incremental: Hir,
+    full: Hir,
+}
+
+impl Hirs {
+    fn new() -> Self {
+        let mut hirs = Self {
+            incremental: Hir::new(),
+            full: Hir::new(),
+        };
+
+        hirs.add("test:///static.d.rhai", STATIC_SRC);
+        hirs.add("test:///a.rhai", A_SRC);
+        hirs.add("test:///b.rhai", B_SRC);
+        hirs.add("test:///c.rhai", C_SRC);
+        hirs.resolve();
+
+        hirs
+    }
+
+    fn add(&mut self, url: &str, src: &str) {
+        let syntax = if src.trim_start().starts_with("module") {
+            Parser::new(src).parse_def().into_syntax()
+        } else {
+            Parser::new(src).parse_script().into_syntax()
+        };
+
+        let url = url.parse().unwrap();
+        self.incremental.add_source(&url, &syntax);
+        self.full.add_source(&url, &syntax);
+    }
+
+    fn remove(&mut self, url: &str) {
+        let url = url.parse().unwrap();
+
+        let source = self.incremental.source_of(&url).unwrap();
+        self.incremental.remove_source(source);
+
+        let source = self.full.source_of(&url).unwrap();
+        self.full.remove_source(source);
+    }
+
+    fn resolve(&mut self) {
+        self.incremental.resolve_changed();
+        self.full.resolve_all();
+
+        assert_same(&self.incremental, &self.full);
+    }
+}
+
+fn assert_same(incremental: &Hir, full: &Hir) {
+    let incremental_errors = incremental
+        .errors()
+        .into_iter()
+        .map(|e| format!("{:?}", e.kind))
+        .collect::<Vec<_>>();
+    let full_errors = full
+        .errors()
+        .into_iter()
+        .map(|e| format!("{:?}", e.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(incremental_errors, full_errors);
+
+    assert_eq!(incremental.symbols().count(), full.symbols().count());
+
+    for ((symbol, data), (full_symbol, full_data)) in incremental.symbols().zip(full.symbols()) {
+        assert_eq!(symbol, full_symbol);
+        assert_eq!(
+            format!("{:?}", data.target()),
+            format!("{:?}", full_data.target()),
+            "different targets of {symbol:?}"
+        );
+        assert_eq!(
+            data.kind.references(),
+            full_data.kind.references(),
+            "different references of {symbol:?}"
+        );
+        assert_eq!(
+            data.ty.fmt(incremental).to_string(),
+            full_data.ty.fmt(full).to_string(),
+            "different types of {symbol:?}"
+        );
+    }
+}
+
+#[test]
+fn test_incremental_edit_imported_module() {
+    let mut hirs = Hirs::new();
+
+    hirs.add(
+        "test:///b.rhai",
+        r#"
+fn foo() {
+    "foo"
+}
+"#,
+    );
+    hirs.resolve();
+
+    hirs.add("test:///b.rhai", B_SRC);
+    hirs.resolve();
+}
+
+#[test]
+fn test_incremental_edit_unrelated_script() {
+    let mut hirs = Hirs::new();
+
+    hirs.add(
+        "test:///c.rhai",
+        r#"
+let z = 3;
+print(z);
+"#,
+    );
+    hirs.resolve();
+}
+
+#[test]
+fn test_incremental_edit_indirectly_imported_module() {
+    let mut hirs = Hirs::new();
+
+    hirs.add(
+        "test:///d.rhai",
+        r#"
+import "./a.rhai" as a;
+
+let v = a::x;
+print(v);
+"#,
+    );
+    hirs.resolve();
+
+    hirs.add(
+        "test:///b.rhai",
+        r#"
+fn foo() {
+    "foo"
+}
+"#,
+    );
+    hirs.resolve();
+}
+
+#[test]
+fn test_incremental_remove_and_add_module() {
+    let mut hirs = Hirs::new();
+
+    hirs.remove("test:///b.rhai");
+    hirs.resolve();
+
+    hirs.add("test:///b.rhai", B_SRC);
+    hirs.resolve();
+}
+
+#[test]
+fn test_incremental_new_importer() {
+    let mut hirs = Hirs::new();
+
+    hirs.add(
+        "test:///d.rhai",
+        r#"
+import "./a.rhai" as a;
+import "./b.rhai" as b;
+
+print(b::foo());
+"#,
+    );
+    hirs.resolve();
+
+    hirs.add("test:///b.rhai", "fn bar() {}\n");
+    hirs.resolve();
+}
+
+#[test]
+fn test_incremental_edit_definitions() {
+    let mut hirs = Hirs::new();
+
+    hirs.add(
+        "test:///static.d.rhai",
+        r#"
+module static;
+
+fn print(value: ?);
+
+fn undefined() -> int;
+"#,
+    );
+    hirs.resolve();
+}
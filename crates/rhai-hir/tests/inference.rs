@@ -0,0 +1,234 @@
+use rhai_hir::Hir;
+use rhai_rowan::parser::Parser;
+
+const DEFS: &str = r#"
+module static;
+
+fn type_of(value: ?) -> String;
+
+fn random() -> bool;
+
+fn parse(value: String) -> int;
+"#;
+
+fn hir_with_script(src: &str) -> Hir {
+    let mut hir = Hir::new();
+
+    hir.add_source(
+        &"test:///static.d.rhai".parse().unwrap(),
+        &Parser::new(DEFS).parse_def().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///root.rhai".parse().unwrap(),
+        &Parser::new(src).parse_script().into_syntax(),
+    );
+
+    hir.resolve_all();
+    hir
+}
+
+/// The formatted type of the function or variable
+/// declared with the given name in the script.
+fn type_of(hir: &Hir, name: &str) -> String {
+    let source = hir
+        .source_by_url(&"test:///root.rhai".parse().unwrap())
+        .unwrap();
+
+    let (_, data) = hir
+        .symbols()
+        .filter(|(_, data)| data.source.is(source))
+        .find(|(_, data)| {
+            (data.kind.is_decl() || data.kind.is_fn()) && data.name(hir) == Some(name)
+        })
+        .unwrap_or_else(|| panic!("no declaration named {name}"));
+
+    data.ty.fmt(hir).to_string()
+}
+
+#[test]
+fn test_return_type_union() {
+    let hir = hir_with_script(
+        r#"
+fn foo(x) {
+    if random() {
+        return "none";
+    }
+
+    if x == 0 {
+        return 1;
+    }
+
+    2.0
+}
+
+fn bar() {
+    let x = 1;
+}
+
+fn baz() {
+    return;
+}
+
+fn factorial(n) {
+    if n < 2 {
+        return 1;
+    }
+
+    n * factorial(n - 1)
+}
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "foo"), "fn (x: ?) -> String | int | float");
+    assert_eq!(type_of(&hir, "bar"), "fn () -> ()");
+    assert_eq!(type_of(&hir, "baz"), "fn () -> ()");
+    assert_eq!(type_of(&hir, "factorial"), "fn (n: ?) -> int");
+}
+
+#[test]
+fn test_closure_return_type() {
+    let hir = hir_with_script(
+        r#"
+let f = |x| {
+    if x {
+        return 'a';
+    }
+
+    let y = 1;
+    y
+};
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "f"), "fn (x: ?) -> char | int");
+}
+
+#[test]
+fn test_returns_of_closures_are_not_part_of_functions() {
+    let hir = hir_with_script(
+        r#"
+fn foo() {
+    let f = || {
+        return "inner";
+    };
+
+    1
+}
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "foo"), "fn () -> int");
+}
+
+#[test]
+fn test_switch_tail_return_type() {
+    let hir = hir_with_script(
+        r#"
+fn tail(x) {
+    switch x {
+        1 => 2,
+        _ => "s"
+    }
+}
+
+fn bound(x) {
+    let y = switch x {
+        1 => 2,
+        _ => "s"
+    };
+    y
+}
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "tail"), "fn (x: ?) -> int | String");
+    assert_eq!(type_of(&hir, "bound"), "fn (x: ?) -> int | String");
+}
+
+#[test]
+fn test_type_of_narrowing() {
+    let hir = hir_with_script(
+        r#"
+let x = if random() { 1 } else { "a" };
+
+if type_of(x) == "i64" {
+    let as_int = x;
+}
+
+if "string" == type_of(x) {
+    let as_string = x;
+} else if type_of(x) == "array" {
+    let as_array = x;
+}
+
+if type_of(x) == "unknown" {
+    let not_narrowed = x;
+}
+
+let unrelated = x;
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "x"), "int | String");
+    assert_eq!(type_of(&hir, "as_int"), "int");
+    assert_eq!(type_of(&hir, "as_string"), "String");
+    assert_eq!(type_of(&hir, "as_array"), "[?]");
+    assert_eq!(type_of(&hir, "not_narrowed"), "int | String");
+    assert_eq!(type_of(&hir, "unrelated"), "int | String");
+}
+
+#[test]
+fn test_nested_narrowing() {
+    let hir = hir_with_script(
+        r#"
+fn foo(x) {
+    if type_of(x) == "map" {
+        if random() {
+            let map = x;
+        }
+    }
+}
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "map"), "#{}");
+}
+
+#[test]
+fn test_array_item_types() {
+    let hir = hir_with_script(
+        r#"
+let empty = [];
+empty[0] = 1;
+
+let arr = [1, 2];
+arr[2] = "three";
+arr[3] = parse("4");
+
+let item = arr[0];
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "empty"), "[int]");
+    assert_eq!(type_of(&hir, "arr"), "[int | String]");
+    assert_eq!(type_of(&hir, "item"), "int | String");
+}
+
+#[test]
+fn test_object_field_types() {
+    let hir = hir_with_script(
+        r#"
+let obj = #{ a: 1 };
+obj.a = "one";
+obj.b = true;
+obj["c"] = 'c';
+
+let b = obj.b;
+let c = obj["c"];
+"#,
+    );
+
+    assert_eq!(type_of(&hir, "obj"), "#{a: int | String, b: bool, c: char}");
+    assert_eq!(type_of(&hir, "b"), "bool");
+    assert_eq!(type_of(&hir, "c"), "char");
+}
@@ -0,0 +1,59 @@
+use rhai_hir::Hir;
+use rhai_rowan::parser::Parser;
+
+fn roundtrip(hir: &Hir) -> Hir {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(hir, &mut bytes).unwrap();
+    ciborium::de::from_reader(bytes.as_slice()).unwrap()
+}
+
+#[test]
+fn test_serialize_hir() {
+    let root_src = r#"
+let a = global::add(1, 2);
+undefined_fn(a);
+"#;
+
+    let global_src = r#"
+module global;
+
+/// Add two integers.
+fn add(a: int, b: int) -> int;
+"#;
+
+    let mut hir = Hir::new();
+
+    hir.add_source(
+        &"test:///root.rhai".parse().unwrap(),
+        &Parser::new(root_src).parse_script().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///global.d.rhai".parse().unwrap(),
+        &Parser::new(global_src).parse_def().into_syntax(),
+    );
+
+    hir.resolve_all();
+
+    let mut cached = roundtrip(&hir);
+
+    assert_eq!(cached.symbols().count(), hir.symbols().count());
+    assert_eq!(cached.errors().len(), 1);
+
+    let root = cached.source_of(&"test:///root.rhai".parse().unwrap());
+    assert!(root.is_some());
+
+    // The deserialized HIR can be updated as usual.
+    let other_src = r#"
+global::add(3, global::add(1, 2));
+"#;
+
+    cached.add_source(
+        &"test:///other.rhai".parse().unwrap(),
+        &Parser::new(other_src).parse_script().into_syntax(),
+    );
+
+    cached.resolve_all();
+
+    assert_eq!(cached.errors().len(), 1);
+    assert_eq!(cached.sources().count(), hir.sources().count() + 1);
+}
@@ -0,0 +1,123 @@
+use rhai_hir::{error::ErrorKind, Hir};
+use rhai_rowan::parser::Parser;
+
+fn error_codes(src: &str) -> Vec<&'static str> {
+    let mut hir = Hir::new();
+
+    let url = "test:///root.rhai".parse().unwrap();
+    hir.add_source(&url, &Parser::new(src).parse_script().into_syntax());
+    hir.resolve_all();
+
+    let source = hir.source_of(&url).unwrap();
+
+    let mut codes = hir
+        .errors_for_source(source)
+        .into_iter()
+        .map(|err| err.kind.code())
+        .collect::<Vec<_>>();
+
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn test_suppress_next_line() {
+    let codes = error_codes(
+        r#"
+// rhai-lsp: allow(unused-variable)
+let a = 1;
+let b = 2;
+"#,
+    );
+
+    assert_eq!(codes, ["unused-variable"]);
+}
+
+#[test]
+fn test_suppress_trailing_comment() {
+    let codes = error_codes(
+        r#"
+let a = 1; // rhai-lsp: allow(unused-variable)
+let b = undefined; // rhai-lsp: allow(unresolved-reference, unused-variable)
+"#,
+    );
+
+    assert!(codes.is_empty(), "{codes:?}");
+}
+
+#[test]
+fn test_suppress_file() {
+    let codes = error_codes(
+        r#"//! rhai-lsp: allow(unused-variable)
+
+let a = 1;
+
+fn f() {
+    let b = 2;
+}
+"#,
+    );
+
+    assert!(codes.is_empty(), "{codes:?}");
+}
+
+#[test]
+fn test_unused_suppression() {
+    let mut hir = Hir::new();
+
+    let url = "test:///root.rhai".parse().unwrap();
+    hir.add_source(
+        &url,
+        &Parser::new(
+            r#"
+// rhai-lsp: allow(unused-variable, nested-function)
+let a = 1;
+"#,
+        )
+        .parse_script()
+        .into_syntax(),
+    );
+    hir.resolve_all();
+
+    let errors = hir.errors_for_source(hir.source_of(&url).unwrap());
+
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(
+        &errors[0].kind,
+        ErrorKind::UnusedSuppression { code, .. } if code == "nested-function"
+    ));
+}
+
+#[test]
+fn test_all_errors_are_suppressed() {
+    let mut hir = Hir::new();
+
+    for (url, src) in [
+        (
+            "test:///suppressed.rhai",
+            "// rhai-lsp: allow(unused-variable, nested-function)\nlet a = 1;\n",
+        ),
+        (
+            "test:///unused.rhai",
+            "// rhai-lsp: allow(unused-variable)\n",
+        ),
+    ] {
+        hir.add_source(
+            &url.parse().unwrap(),
+            &Parser::new(src).parse_script().into_syntax(),
+        );
+    }
+    hir.resolve_all();
+
+    let mut codes = hir
+        .errors()
+        .into_iter()
+        .map(|err| match err.kind {
+            ErrorKind::UnusedSuppression { code, .. } => code,
+            kind => panic!("unexpected error {kind:?}"),
+        })
+        .collect::<Vec<_>>();
+    codes.sort_unstable();
+
+    assert_eq!(codes, ["nested-function", "unused-variable"]);
+}
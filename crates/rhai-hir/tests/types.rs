@@ -0,0 +1,267 @@
+use rhai_hir::{
+    error::{Error, ErrorKind},
+    symbol::ReferenceTarget,
+    Hir, Symbol,
+};
+use rhai_rowan::parser::Parser;
+
+const DEFS: &str = r#"
+module static;
+
+fn print(data: ?);
+
+fn add(a: int, b: int) -> int;
+
+fn call(fn_ptr: FnPtr, ...args: ?) -> ?;
+
+fn call(obj: ?, fn_ptr: FnPtr, ...args: ?) -> ?;
+
+fn on_event(name: String) -> bool;
+
+fn parse(value: String) -> int;
+
+fn parse(value: String, radix: int) -> int;
+
+fn parse(value: bool) -> String;
+
+op +(int, int) -> int;
+op +(String, String) -> String;
+op -(int) -> int;
+"#;
+
+fn hir_with_script(src: &str) -> Hir {
+    let mut hir = Hir::new();
+
+    hir.add_source(
+        &"test:///static.d.rhai".parse().unwrap(),
+        &Parser::new(DEFS).parse_def().into_syntax(),
+    );
+    hir.add_source(
+        &"test:///root.rhai".parse().unwrap(),
+        &Parser::new(src).parse_script().into_syntax(),
+    );
+
+    hir.resolve_all();
+    hir
+}
+
+/// Errors without the unused symbol lints,
+/// most scripts here are not meant to be complete.
+fn type_errors(hir: &Hir) -> Vec<Error> {
+    hir.errors()
+        .into_iter()
+        .filter(|err| !err.kind.is_unused())
+        .collect()
+}
+
+#[test]
+fn test_valid_calls() {
+    let hir = hir_with_script(
+        r#"
+print("hello");
+let x = add(1, 2);
+call(x, 1, 2, 3);
+call(x);
+"#,
+    );
+
+    assert!(type_errors(&hir).is_empty(), "{:#?}", type_errors(&hir));
+}
+
+#[test]
+fn test_argument_count_mismatch() {
+    let hir = hir_with_script(
+        r#"
+add(1);
+call();
+"#,
+    );
+
+    let errors = type_errors(&hir);
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+
+    for error in errors {
+        assert!(matches!(
+            error.kind,
+            ErrorKind::ArgumentCountMismatch {
+                found: 1,
+                expected: 2,
+                variadic: false,
+                ..
+            } | ErrorKind::ArgumentCountMismatch {
+                found: 0,
+                expected: 1,
+                variadic: true,
+                ..
+            }
+        ));
+    }
+}
+
+#[test]
+fn test_argument_type_mismatch() {
+    let hir = hir_with_script(
+        r#"
+add(1, "2");
+"#,
+    );
+
+    let errors = type_errors(&hir);
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(
+        &errors[0].kind,
+        ErrorKind::ArgumentTypeMismatch { expected, found, .. } if expected == "int" && found == "String"
+    ));
+}
+
+#[test]
+fn test_operand_type_mismatch() {
+    let hir = hir_with_script(
+        r#"
+let a = 1 + 2;
+let b = "a" + "b";
+let c = 1 + "b";
+let d = -"a";
+"#,
+    );
+
+    let errors = type_errors(&hir);
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+    assert!(errors
+        .iter()
+        .all(|err| matches!(err.kind, ErrorKind::OperandTypeMismatch { .. })));
+}
+
+#[test]
+fn test_return_type_mismatch() {
+    let hir = hir_with_script(
+        r#"
+fn on_event(name) {
+    if name == "" {
+        return false;
+    }
+
+    "handled"
+}
+"#,
+    );
+
+    let errors = type_errors(&hir);
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(
+        &errors[0].kind,
+        ErrorKind::ReturnTypeMismatch { expected, found, .. } if expected == "bool" && found == "String"
+    ));
+}
+
+/// The function targeted by each call in the script in source order.
+fn call_targets(hir: &Hir) -> Vec<Symbol> {
+    let source = hir
+        .source_by_url(&"test:///root.rhai".parse().unwrap())
+        .unwrap();
+
+    let mut calls = hir
+        .symbols()
+        .filter(|(_, data)| data.source.is(source))
+        .filter_map(|(_, data)| Some((data.text_range()?, data.kind.as_call()?)))
+        .collect::<Vec<_>>();
+
+    calls.sort_by_key(|(range, _)| range.start());
+
+    calls
+        .into_iter()
+        .map(|(_, call)| {
+            let reference = hir.call_reference(call).unwrap();
+            match hir[reference].target() {
+                Some(ReferenceTarget::Symbol(target)) => target,
+                _ => panic!("unresolved call"),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_overload_resolution() {
+    let hir = hir_with_script(
+        r#"
+let a = parse("1");
+let b = parse("1", 16);
+let c = parse(true);
+"#,
+    );
+
+    assert!(type_errors(&hir).is_empty(), "{:#?}", type_errors(&hir));
+
+    let targets = call_targets(&hir);
+    assert_eq!(targets.len(), 3);
+
+    let signatures = targets
+        .iter()
+        .map(|&target| hir[target].ty.fmt(&hir).to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        signatures,
+        [
+            "fn (value: String) -> int",
+            "fn (value: String, radix: int) -> int",
+            "fn (value: bool) -> String",
+        ]
+    );
+
+    for target in targets {
+        assert_eq!(hir[target].kind.as_fn().unwrap().references.len(), 1);
+    }
+
+    let c = hir
+        .symbols()
+        .find(|(_, data)| data.kind.as_decl().is_some_and(|d| d.name == "c"))
+        .unwrap()
+        .1;
+    assert_eq!(c.ty.fmt(&hir).to_string(), "String");
+}
+
+#[test]
+fn test_variadic_overload_resolution() {
+    let hir = hir_with_script(
+        r#"
+let f = 0;
+call(f);
+call(#{}, f, 1, 2);
+"#,
+    );
+
+    let param_counts = call_targets(&hir)
+        .iter()
+        .map(|&target| hir[target].kind.as_fn().unwrap().params.len())
+        .collect::<Vec<_>>();
+
+    assert_eq!(param_counts, [2, 3]);
+}
+
+#[test]
+fn test_script_overload_resolution() {
+    let hir = hir_with_script(
+        r#"
+fn area(size) {
+    size * size
+}
+
+fn area(width, height) {
+    width * height
+}
+
+area(1, 2);
+area(1);
+"#,
+    );
+
+    assert!(type_errors(&hir).is_empty(), "{:#?}", type_errors(&hir));
+
+    let param_counts = call_targets(&hir)
+        .iter()
+        .map(|&target| hir[target].kind.as_fn().unwrap().params.len())
+        .collect::<Vec<_>>();
+
+    assert_eq!(param_counts, [2, 1]);
+}
@@ -0,0 +1,105 @@
+use rhai_hir::{error::ErrorKind, Hir};
+use rhai_rowan::parser::Parser;
+
+fn hir_with_script(src: &str) -> Hir {
+    let mut hir = Hir::new();
+
+    hir.add_source(
+        &"test:///root.rhai".parse().unwrap(),
+        &Parser::new(src).parse_script().into_syntax(),
+    );
+
+    hir.resolve_all();
+    hir
+}
+
+fn unused_names(hir: &Hir) -> Vec<String> {
+    let mut names = hir
+        .errors()
+        .into_iter()
+        .filter_map(|err| match err.kind {
+            ErrorKind::UnusedVariable { name, .. }
+            | ErrorKind::UnusedParameter { name, .. }
+            | ErrorKind::UnusedFunction { name, .. }
+            | ErrorKind::UnusedImport { name, .. } => Some(name),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    names.sort();
+    names
+}
+
+#[test]
+fn test_unused_symbols() {
+    let hir = hir_with_script(
+        r#"
+import "./module.rhai" as m;
+
+private fn helper(a, b) {
+    let c = 1;
+    a
+}
+
+fn public() {}
+
+let x = 1;
+"#,
+    );
+
+    assert_eq!(unused_names(&hir), ["b", "c", "helper", "m", "x"]);
+}
+
+#[test]
+fn test_used_symbols() {
+    let hir = hir_with_script(
+        r#"
+import "./module.rhai" as m;
+
+private fn helper(a) {
+    a + 1
+}
+
+let x = 1;
+let y = `${x}`;
+let f = |value| helper(value);
+
+m::something(f, y);
+"#,
+    );
+
+    assert!(unused_names(&hir).is_empty(), "{:?}", unused_names(&hir));
+}
+
+#[test]
+fn test_unused_exemptions() {
+    let hir = hir_with_script(
+        r#"
+fn handler(_event, _) {
+    let _ignored = 1;
+}
+
+export let exported = 1;
+
+for item in [] {}
+"#,
+    );
+
+    assert!(unused_names(&hir).is_empty(), "{:?}", unused_names(&hir));
+}
+
+#[test]
+fn test_method_call_usage() {
+    let hir = hir_with_script(
+        r#"
+private fn double() {
+    this * 2
+}
+
+let x = 1;
+x.double();
+"#,
+    );
+
+    assert!(unused_names(&hir).is_empty(), "{:?}", unused_names(&hir));
+}
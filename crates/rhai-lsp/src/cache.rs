@@ -0,0 +1,252 @@
+//! On-disk cache of the HIR of workspaces.
+//!
+//! Every workspace has a single cache file in the `cachePath` directory
+//! given in the initialization options. The HIR is stored as a whole,
+//! as symbols of different sources refer to each other, along with the
+//! content hash of every source it was built from.
+//!
+//! The cached HIR is only used if it was written by the same version
+//! of the server with the same module configuration, sources that
+//! changed since then are parsed and resolved again.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use lsp_types::Url;
+use rhai_common::{config::Config, environment::Environment};
+use rhai_hir::Hir;
+use serde::{Deserialize, Serialize};
+
+use crate::IndexMap;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The cached sources by their URLs.
+pub(crate) type CachedSources = IndexMap<Url, CachedSource>;
+
+/// A source that the cached HIR was built from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CachedSource {
+    /// Hash of the contents of the source.
+    pub(crate) hash: u64,
+    /// Whether the source has syntax errors.
+    pub(crate) syntax_errors: bool,
+}
+
+#[derive(Serialize)]
+struct CacheEntryRef<'h> {
+    version: &'h str,
+    config: u64,
+    sources: &'h CachedSources,
+    hir: &'h Hir,
+}
+
+#[derive(Deserialize)]
+struct CacheEntry {
+    version: String,
+    config: u64,
+    sources: CachedSources,
+    hir: Hir,
+}
+
+/// A HIR loaded from the cache.
+pub(crate) struct CachedHir {
+    pub(crate) hir: Hir,
+    /// The sources the HIR was built from.
+    pub(crate) sources: CachedSources,
+}
+
+/// The cache file of the workspace with the given root.
+pub(crate) fn cache_file(cache_path: &Path, root: &Url) -> PathBuf {
+    cache_path.join(format!("hir-{:016x}.cbor", hash(root.as_str())))
+}
+
+/// Hash of the configuration that the HIR depends on.
+///
+/// The module configuration decides the targets of imports.
+pub(crate) fn config_hash(config: &Config) -> u64 {
+    hash(serde_json::to_string(&config.modules).unwrap_or_default())
+}
+
+/// Hash of the contents of a source.
+pub(crate) fn source_hash(text: &str) -> u64 {
+    hash(text)
+}
+
+/// Load the cached HIR if it was built with the same
+/// server version and configuration.
+///
+/// The module resolver of the returned HIR has to be set again.
+pub(crate) async fn load_hir<E: Environment>(
+    env: &E,
+    path: &Path,
+    config: u64,
+) -> Option<CachedHir> {
+    let bytes = match env.read_file(path).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::debug!(%error, ?path, "no HIR cache");
+            return None;
+        }
+    };
+
+    let entry: CacheEntry = match ciborium::de::from_reader(bytes.as_slice()) {
+        Ok(entry) => entry,
+        Err(error) => {
+            tracing::warn!(%error, ?path, "invalid HIR cache");
+            return None;
+        }
+    };
+
+    if entry.version != VERSION || entry.config != config {
+        tracing::debug!(?path, "outdated HIR cache");
+        return None;
+    }
+
+    Some(CachedHir {
+        hir: entry.hir,
+        sources: entry.sources,
+    })
+}
+
+/// Write the HIR built from the given sources to the cache,
+/// errors are only logged.
+pub(crate) async fn store_hir<E: Environment>(
+    env: &E,
+    path: &Path,
+    config: u64,
+    sources: &CachedSources,
+    hir: &Hir,
+) {
+    let mut bytes = Vec::new();
+
+    let entry = CacheEntryRef {
+        version: VERSION,
+        config,
+        sources,
+        hir,
+    };
+
+    if let Err(error) = ciborium::ser::into_writer(&entry, &mut bytes) {
+        tracing::warn!(%error, "failed to serialize HIR");
+        return;
+    }
+
+    if let Some(dir) = path.parent() {
+        if let Err(error) = env.create_dir_all(dir).await {
+            tracing::warn!(%error, ?dir, "failed to create the cache directory");
+            return;
+        }
+    }
+
+    if let Err(error) = env.write_file(path, &bytes).await {
+        tracing::warn!(%error, ?path, "failed to write HIR cache");
+    }
+}
+
+fn hash(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_common::environment::native::NativeEnvironment;
+    use rhai_rowan::parser::Parser;
+
+    fn hir_with_script(src: &str) -> Hir {
+        let mut hir = Hir::new();
+        hir.add_source(
+            &"test:///root.rhai".parse().unwrap(),
+            &Parser::new(src).parse_script().into_syntax(),
+        );
+        hir.resolve_all();
+        hir
+    }
+
+    fn sources(text: &str) -> CachedSources {
+        let mut sources = CachedSources::default();
+        sources.insert(
+            "test:///root.rhai".parse().unwrap(),
+            CachedSource {
+                hash: source_hash(text),
+                syntax_errors: false,
+            },
+        );
+        sources
+    }
+
+    #[test]
+    fn test_hashes() {
+        assert_eq!(source_hash("let a = 1;"), source_hash("let a = 1;"));
+        assert_ne!(source_hash("let a = 1;"), source_hash("let a = 2;"));
+
+        let config = Config::default();
+        let mut other_config = Config::default();
+        other_config
+            .modules
+            .names
+            .insert("lib".into(), "lib.rhai".into());
+
+        assert_eq!(config_hash(&config), config_hash(&Config::default()));
+        assert_ne!(config_hash(&config), config_hash(&other_config));
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        // The cache directory does not exist yet.
+        let path = cache_file(&dir.path().join("cache"), &"file:///root".parse().unwrap());
+
+        let src = "let a = 1;";
+        let hir = hir_with_script(src);
+
+        store_hir(&NativeEnvironment, &path, 1, &sources(src), &hir).await;
+
+        let cached = load_hir(&NativeEnvironment, &path, 1).await.unwrap();
+        assert_eq!(cached.sources, sources(src));
+        assert_eq!(cached.hir.symbols().count(), hir.symbols().count());
+
+        assert!(load_hir(&NativeEnvironment, &path, 2).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hir.cbor");
+
+        let src = "let a = 1;";
+        let hir = hir_with_script(src);
+
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(
+            &CacheEntryRef {
+                version: "0.0.0-outdated",
+                config: 1,
+                sources: &sources(src),
+                hir: &hir,
+            },
+            &mut bytes,
+        )
+        .unwrap();
+        std::fs::write(&path, bytes).unwrap();
+
+        assert!(load_hir(&NativeEnvironment, &path, 1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hir.cbor");
+
+        assert!(load_hir(&NativeEnvironment, &path, 1).await.is_none());
+
+        std::fs::write(&path, b"not a cache entry").unwrap();
+        assert!(load_hir(&NativeEnvironment, &path, 1).await.is_none());
+    }
+}
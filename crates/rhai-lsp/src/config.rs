@@ -28,6 +28,7 @@ fn default_configuration_section() -> String {
 #[serde(rename_all = "camelCase")]
 pub struct LspConfig {
     pub syntax: SyntaxConfig,
+    pub inlay_hints: InlayHintsConfig,
     pub debug: DebugConfig,
 }
 
@@ -55,6 +56,27 @@ impl Default for SyntaxConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHintsConfig {
+    /// Show the inferred types of `let` bindings without a type.
+    pub variable_types: bool,
+    /// Show parameter names at calls of functions from definitions.
+    pub parameter_names: bool,
+    /// Show the inferred return types of script functions.
+    pub return_types: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            variable_types: true,
+            parameter_names: true,
+            return_types: true,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugConfig {
@@ -1,11 +1,15 @@
-use crate::world::{Document, World};
+use crate::world::{Document, Workspace, World};
 use lsp_async_stub::{util::LspExt, Context, RequestWriter};
 use lsp_types::{
-    notification, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
-    PublishDiagnosticsParams, Url,
+    notification, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag,
+    Location, NumberOrString, PublishDiagnosticsParams, Url,
 };
-use rhai_common::{environment::Environment, util::Normalize};
-use rhai_hir::{error::ErrorKind, Hir};
+use rhai_common::{
+    config::{LintConfig, LintLevel},
+    environment::Environment,
+    util::Normalize,
+};
+use rhai_hir::{error::ErrorKind, Symbol};
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn publish_all_diagnostics<E: Environment>(context: Context<World<E>>) {
@@ -39,7 +43,8 @@ pub(crate) async fn publish_diagnostics<E: Environment>(
         None => return,
     };
 
-    collect_syntax_errors(doc, &mut diags);
+    collect_syntax_errors(doc, &ws.rhai_config.lint, &mut diags);
+    let has_syntax_errors = !doc.syntax_errors().is_empty();
     drop(workspaces);
 
     context
@@ -51,7 +56,9 @@ pub(crate) async fn publish_diagnostics<E: Environment>(
         .await
         .unwrap_or_else(|err| tracing::error!("{err}"));
 
-    if !diags.is_empty() {
+    // Semantic errors are not reliable
+    // in documents with syntax errors.
+    if has_syntax_errors {
         return;
     }
 
@@ -63,7 +70,7 @@ pub(crate) async fn publish_diagnostics<E: Environment>(
         None => return,
     };
 
-    collect_hir_errors(&document_url.clone().normalize(), doc, &ws.hir, &mut diags);
+    collect_hir_errors(&document_url.clone().normalize(), doc, ws, &mut diags);
     drop(workspaces);
 
     context.clone().env.spawn_local(async move {
@@ -96,117 +103,131 @@ pub(crate) async fn clear_diagnostics<E: Environment>(
 }
 
 #[tracing::instrument(skip_all)]
-fn collect_syntax_errors(doc: &Document, diags: &mut Vec<Diagnostic>) {
-    diags.extend(doc.parse.errors.iter().map(|e| {
+fn collect_syntax_errors(doc: &Document, lint: &LintConfig, diags: &mut Vec<Diagnostic>) {
+    diags.extend(doc.syntax_errors().iter().filter_map(|e| {
+        let severity = match lint.level_for_syntax_error(&e.kind) {
+            LintLevel::Allow => return None,
+            LintLevel::Warn => DiagnosticSeverity::WARNING,
+            LintLevel::Error => DiagnosticSeverity::ERROR,
+        };
+
         let range = doc.mapper.range(e.range).unwrap_or_default().into_lsp();
-        Diagnostic {
+        Some(Diagnostic {
             range,
-            severity: Some(DiagnosticSeverity::ERROR),
-            code: None,
+            severity: Some(severity),
+            code: Some(NumberOrString::String(e.kind.code().into())),
             code_description: None,
             source: Some("Rhai".into()),
             message: e.kind.to_string(),
             related_information: None,
             tags: None,
             data: None,
-        }
+        })
     }));
 }
 
 #[tracing::instrument(skip_all)]
-fn collect_hir_errors(uri: &Url, doc: &Document, hir: &Hir, diags: &mut Vec<Diagnostic>) {
+fn collect_hir_errors<E: Environment>(
+    uri: &Url,
+    doc: &Document,
+    ws: &Workspace<E>,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let hir = &ws.hir;
+
     if let Some(source) = hir.source_by_url(uri) {
         for error in hir.errors_for_source(source) {
+            let severity = match ws.rhai_config.lint.level_for(&error.kind) {
+                LintLevel::Allow => continue,
+                LintLevel::Warn => DiagnosticSeverity::WARNING,
+                LintLevel::Error => DiagnosticSeverity::ERROR,
+            };
+
+            let range = hir
+                .error_range(&error)
+                .and_then(|range| doc.mapper.range(range))
+                .unwrap_or_default()
+                .into_lsp();
+
+            let mut diagnostic = Diagnostic {
+                range,
+                severity: Some(severity),
+                code: Some(NumberOrString::String(error.kind.code().into())),
+                code_description: None,
+                source: Some("Rhai".into()),
+                message: error.to_string(),
+                related_information: None,
+                tags: None,
+                // Code actions are built from the error data.
+                data: serde_json::to_value(&error.kind).ok(),
+            };
+
             match &error.kind {
                 ErrorKind::DuplicateFnParameter {
-                    duplicate_symbol,
-                    existing_symbol,
-                } => diags.push(Diagnostic {
-                    range: doc
-                        .mapper
-                        .range(
-                            hir[*duplicate_symbol]
-                                .selection_or_text_range()
-                                .unwrap_or_default(),
-                        )
-                        .unwrap_or_default()
-                        .into_lsp(),
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: None,
-                    code_description: None,
-                    source: Some("Rhai".into()),
-                    message: error.to_string(),
-                    related_information: Some(Vec::from([DiagnosticRelatedInformation {
-                        message: "parameter with the same name".into(),
-                        location: Location {
-                            range: doc
-                                .mapper
-                                .range(
-                                    hir[*existing_symbol]
-                                        .selection_or_text_range()
-                                        .unwrap_or_default(),
-                                )
-                                .unwrap_or_default()
-                                .into_lsp(),
-                            uri: uri.clone(),
-                        },
-                    }])),
-                    tags: None,
-                    data: None,
-                }),
-                ErrorKind::UnresolvedReference {
-                    reference_symbol,
-                    similar_name: _,
-                } => diags.push(Diagnostic {
-                    range: doc
-                        .mapper
-                        .range(
-                            hir[*reference_symbol]
-                                .selection_or_text_range()
-                                .unwrap_or_default(),
-                        )
-                        .unwrap_or_default()
-                        .into_lsp(),
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: None,
-                    code_description: None,
-                    source: Some("Rhai".into()),
-                    message: error.to_string(),
-                    related_information: None,
-                    tags: None,
-                    data: None,
-                }),
-                ErrorKind::UnresolvedImport { import } => diags.push(Diagnostic {
-                    range: doc
-                        .mapper
-                        .range(hir[*import].selection_or_text_range().unwrap_or_default())
-                        .unwrap_or_default()
-                        .into_lsp(),
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: None,
-                    code_description: None,
-                    source: Some("Rhai".into()),
-                    message: error.to_string(),
-                    related_information: None,
-                    tags: None,
-                    data: None,
-                }),
-                ErrorKind::NestedFunction { function } => diags.push(Diagnostic {
-                    range: doc
-                        .mapper
-                        .range(hir[*function].selection_or_text_range().unwrap_or_default())
-                        .unwrap_or_default()
-                        .into_lsp(),
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: None,
-                    code_description: None,
-                    source: Some("Rhai".into()),
-                    message: error.to_string(),
-                    related_information: None,
-                    tags: None,
-                    data: None,
-                }),
+                    existing_symbol, ..
+                } => {
+                    diagnostic.related_information = related_symbol_information(
+                        ws,
+                        *existing_symbol,
+                        "parameter with the same name",
+                    );
+                }
+                ErrorKind::CircularImport { cycle, .. } => {
+                    diagnostic.related_information = Some(
+                        cycle
+                            .iter()
+                            .filter_map(|&next| {
+                                related_symbol_information(ws, next, "next import in the cycle")
+                            })
+                            .flatten()
+                            .collect(),
+                    );
+                }
+                ErrorKind::ArgumentCountMismatch { function, .. } => {
+                    diagnostic.related_information =
+                        related_symbol_information(ws, *function, "function declared here");
+                }
+                ErrorKind::ArgumentTypeMismatch { param, .. } => {
+                    diagnostic.related_information =
+                        related_symbol_information(ws, *param, "parameter declared here");
+                }
+                ErrorKind::ReturnTypeMismatch { declaration, .. } => {
+                    diagnostic.related_information =
+                        related_symbol_information(ws, *declaration, "return type declared here");
+                }
+                kind if kind.is_unused() => {
+                    // Editors usually fade out unnecessary code.
+                    diagnostic.tags = Some(Vec::from([DiagnosticTag::UNNECESSARY]));
+                }
+                _ => {}
             }
+
+            diags.push(diagnostic);
         }
     }
 }
+
+/// Related information pointing to a symbol
+/// that might be in a different document.
+fn related_symbol_information<E: Environment>(
+    ws: &Workspace<E>,
+    symbol: Symbol,
+    message: &str,
+) -> Option<Vec<DiagnosticRelatedInformation>> {
+    let symbol_data = &ws.hir[symbol];
+    let url = &ws.hir[symbol_data.source.source?].url;
+    let doc = ws.documents.get(url)?;
+
+    let range = doc
+        .mapper
+        .range(symbol_data.selection_or_text_range()?)?
+        .into_lsp();
+
+    Some(Vec::from([DiagnosticRelatedInformation {
+        message: message.into(),
+        location: Location {
+            uri: url.clone(),
+            range,
+        },
+    }]))
+}
@@ -13,15 +13,24 @@ pub(crate) use workspaces::*;
 mod folding_ranges;
 pub(crate) use folding_ranges::*;
 
+mod selection_range;
+pub(crate) use selection_range::*;
+
 mod goto;
 pub(crate) use goto::*;
 
 mod references;
 pub(crate) use references::*;
 
+mod document_highlight;
+pub(crate) use document_highlight::*;
+
 mod document_symbols;
 pub(crate) use document_symbols::*;
 
+mod workspace_symbols;
+pub(crate) use workspace_symbols::*;
+
 mod syntax_tree;
 pub(crate) use syntax_tree::*;
 
@@ -46,5 +55,20 @@ pub(crate) use semantic_tokens::*;
 mod debug;
 pub(crate) use debug::*;
 
+mod module_graph;
+pub(crate) use module_graph::*;
+
 mod formatting;
 pub(crate) use formatting::*;
+
+mod signature_help;
+pub(crate) use signature_help::*;
+
+mod inlay_hints;
+pub(crate) use inlay_hints::*;
+
+mod code_action;
+pub(crate) use code_action::*;
+
+mod call_hierarchy;
+pub(crate) use call_hierarchy::*;
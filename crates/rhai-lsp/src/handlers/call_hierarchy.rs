@@ -0,0 +1,276 @@
+use crate::{
+    utils::signature_of,
+    world::{Workspace, World},
+    IndexMap,
+};
+use lsp_async_stub::{rpc, util::LspExt, Context, Params};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams, Range,
+    SymbolKind,
+};
+use rhai_common::{environment::Environment, util::Normalize};
+use rhai_hir::{
+    source::Source,
+    symbol::{ReferenceTarget, SymbolKind as HirSymbolKind},
+    Symbol,
+};
+use rhai_rowan::TextRange;
+
+/// Calls are either made from a function
+/// or from the top level of a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Caller {
+    Fn(Symbol),
+    Source(Source),
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn prepare_call_hierarchy<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<CallHierarchyPrepareParams>,
+) -> Result<Option<Vec<CallHierarchyItem>>, rpc::Error> {
+    let p = params.required()?;
+
+    let uri = p.text_document_position_params.text_document.uri;
+    let pos = p.text_document_position_params.position;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&uri);
+
+    let doc = ws.document(&uri)?;
+
+    let Some(offset) = doc
+        .mapper
+        .offset(lsp_async_stub::util::Position::from_lsp(pos))
+    else {
+        return Ok(None);
+    };
+
+    let Some(source) = ws.hir.source_of(&uri.clone().normalize()) else {
+        return Ok(None);
+    };
+
+    let Some(symbol) = ws.hir.symbol_selection_at(source, offset, true) else {
+        return Ok(None);
+    };
+
+    let function = match &ws.hir[symbol].kind {
+        HirSymbolKind::Fn(_) => symbol,
+        HirSymbolKind::Ref(r) => match r.target {
+            Some(ReferenceTarget::Symbol(target)) if ws.hir[target].kind.is_fn() => target,
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(call_hierarchy_item(ws, Caller::Fn(function)).map(|item| vec![item]))
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn incoming_calls<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<CallHierarchyIncomingCallsParams>,
+) -> Result<Option<Vec<CallHierarchyIncomingCall>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&p.item.uri);
+
+    let Some(Caller::Fn(function)) = caller_of_item(ws, &p.item) else {
+        return Ok(None);
+    };
+
+    let Some(references) = ws.hir[function].kind.as_fn().map(|f| &f.references) else {
+        return Ok(None);
+    };
+
+    let mut callers: IndexMap<Caller, Vec<Range>> = IndexMap::default();
+
+    for &reference in references {
+        let reference_data = &ws.hir[reference];
+
+        let Some(source) = reference_data.source.source else {
+            continue;
+        };
+
+        let Some(range) = reference_data
+            .selection_or_text_range()
+            .and_then(|range| lsp_range(ws, source, range))
+        else {
+            continue;
+        };
+
+        let caller = ws
+            .hir
+            .containing_fn(reference, false)
+            .map_or(Caller::Source(source), Caller::Fn);
+
+        callers.entry(caller).or_default().push(range);
+    }
+
+    Ok(Some(
+        callers
+            .into_iter()
+            .filter_map(|(caller, from_ranges)| {
+                Some(CallHierarchyIncomingCall {
+                    from: call_hierarchy_item(ws, caller)?,
+                    from_ranges,
+                })
+            })
+            .collect(),
+    ))
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn outgoing_calls<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<CallHierarchyOutgoingCallsParams>,
+) -> Result<Option<Vec<CallHierarchyOutgoingCall>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&p.item.uri);
+
+    let Some(caller) = caller_of_item(ws, &p.item) else {
+        return Ok(None);
+    };
+
+    let symbols: Box<dyn Iterator<Item = Symbol>> = match caller {
+        Caller::Fn(function) => match ws.hir[function].kind.as_fn() {
+            Some(f) => Box::new(ws.hir.descendant_symbols(f.scope)),
+            None => return Ok(None),
+        },
+        Caller::Source(source) => {
+            let Some(module) = ws.hir.module_by_source(source) else {
+                return Ok(None);
+            };
+
+            Box::new(
+                ws.hir
+                    .descendant_symbols(ws.hir[module].scope)
+                    .filter(move |&symbol| {
+                        ws.hir[symbol].source.is(source)
+                            && ws.hir.containing_fn(symbol, false).is_none()
+                    }),
+            )
+        }
+    };
+
+    let mut callees: IndexMap<Symbol, Vec<Range>> = IndexMap::default();
+
+    for symbol in symbols {
+        let HirSymbolKind::Call(call) = &ws.hir[symbol].kind else {
+            continue;
+        };
+
+        let (Some(reference), Some(target)) =
+            (ws.hir.call_reference(call), ws.hir.call_target(call))
+        else {
+            continue;
+        };
+
+        let reference_data = &ws.hir[reference];
+
+        let Some(range) = reference_data.source.source.and_then(|source| {
+            reference_data
+                .selection_or_text_range()
+                .and_then(|range| lsp_range(ws, source, range))
+        }) else {
+            continue;
+        };
+
+        callees.entry(target).or_default().push(range);
+    }
+
+    Ok(Some(
+        callees
+            .into_iter()
+            .filter_map(|(callee, from_ranges)| {
+                Some(CallHierarchyOutgoingCall {
+                    to: call_hierarchy_item(ws, Caller::Fn(callee))?,
+                    from_ranges,
+                })
+            })
+            .collect(),
+    ))
+}
+
+fn call_hierarchy_item<E: Environment>(
+    ws: &Workspace<E>,
+    caller: Caller,
+) -> Option<CallHierarchyItem> {
+    match caller {
+        Caller::Fn(function) => {
+            let symbol_data = ws.hir.symbol(function)?;
+            let source = symbol_data.source.source?;
+
+            let range = lsp_range(ws, source, symbol_data.text_range()?)?;
+            let selection_range = symbol_data
+                .selection_range()
+                .and_then(|range| lsp_range(ws, source, range))
+                .unwrap_or(range);
+
+            Some(CallHierarchyItem {
+                name: symbol_data.name(&ws.hir)?.to_string(),
+                kind: SymbolKind::FUNCTION,
+                tags: None,
+                detail: Some(signature_of(&ws.hir, function)),
+                uri: ws.hir[source].url.clone(),
+                range,
+                selection_range,
+                data: None,
+            })
+        }
+        Caller::Source(source) => {
+            let url = &ws.hir[source].url;
+            let doc = ws.documents.get(url)?;
+
+            Some(CallHierarchyItem {
+                name: url
+                    .path_segments()
+                    .and_then(Iterator::last)
+                    .unwrap_or(url.as_str())
+                    .to_string(),
+                kind: SymbolKind::FILE,
+                tags: None,
+                detail: None,
+                uri: url.clone(),
+                range: doc.mapper.all_range().into_lsp(),
+                selection_range: doc.mapper.range(TextRange::default())?.into_lsp(),
+                data: None,
+            })
+        }
+    }
+}
+
+/// Find the function or script an item was created for.
+fn caller_of_item<E: Environment>(ws: &Workspace<E>, item: &CallHierarchyItem) -> Option<Caller> {
+    let source = ws.hir.source_of(&item.uri.clone().normalize())?;
+
+    if item.kind == SymbolKind::FILE {
+        return Some(Caller::Source(source));
+    }
+
+    let doc = ws.documents.get(&ws.hir[source].url)?;
+    let selection_range = doc
+        .mapper
+        .text_range(lsp_async_stub::util::Range::from_lsp(item.selection_range))?;
+
+    ws.hir
+        .symbols()
+        .find(|(_, symbol_data)| {
+            symbol_data.kind.is_fn()
+                && symbol_data.source.is(source)
+                && symbol_data.has_selection_range(selection_range)
+        })
+        .map(|(symbol, _)| Caller::Fn(symbol))
+}
+
+fn lsp_range<E: Environment>(ws: &Workspace<E>, source: Source, range: TextRange) -> Option<Range> {
+    ws.documents
+        .get(&ws.hir[source].url)?
+        .mapper
+        .range(range)
+        .map(LspExt::into_lsp)
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+
+use crate::world::{Document, Workspace, World};
+use lsp_async_stub::{rpc, util::LspExt, Context, Params};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    Diagnostic, TextEdit, Url, WorkspaceEdit,
+};
+use rhai_common::{environment::Environment, util::Normalize};
+use rhai_hir::{error::ErrorKind, source::Source, Module, Symbol};
+use rhai_rowan::{
+    ast::{AstNode, Expr, Rhai},
+    syntax::SyntaxKind::*,
+    util::{is_valid_ident, trimmed_range},
+    TextRange, TextSize,
+};
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn code_action<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<CodeActionParams>,
+) -> Result<Option<CodeActionResponse>, rpc::Error> {
+    let p = params.required()?;
+
+    let uri = p.text_document.uri;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&uri);
+
+    let doc = ws.document(&uri)?;
+
+    let Some(source) = ws.hir.source_of(&uri.clone().normalize()) else {
+        return Ok(None);
+    };
+
+    let mut actions = Vec::new();
+
+    for diagnostic in &p.context.diagnostics {
+        let Some(error) = diagnostic
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<ErrorKind>(data).ok())
+        else {
+            continue;
+        };
+
+        match error {
+            ErrorKind::UnresolvedReference {
+                reference_symbol,
+                similar_name,
+            } => {
+                if let Some(name) = similar_name {
+                    actions.push(quick_fix(
+                        format!("Replace with `{name}`"),
+                        &uri,
+                        Vec::from([TextEdit {
+                            range: diagnostic.range,
+                            new_text: name,
+                        }]),
+                        diagnostic,
+                        true,
+                    ));
+                }
+
+                actions.extend(import_fixes(
+                    ws,
+                    doc,
+                    &uri,
+                    source,
+                    reference_symbol,
+                    diagnostic,
+                ));
+            }
+            ErrorKind::DuplicateFnParameter { .. } => {
+                actions.push(quick_fix(
+                    "Rename parameter to `_`".into(),
+                    &uri,
+                    Vec::from([TextEdit {
+                        range: diagnostic.range,
+                        new_text: "_".into(),
+                    }]),
+                    diagnostic,
+                    true,
+                ));
+            }
+            ErrorKind::NestedFunction { function } => {
+                actions.extend(move_to_top_level_fix(
+                    ws, doc, &uri, source, function, diagnostic,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Some(actions))
+}
+
+fn quick_fix(
+    title: String,
+    uri: &Url,
+    edits: Vec<TextEdit>,
+    diagnostic: &Diagnostic,
+    is_preferred: bool,
+) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(Vec::from([diagnostic.clone()])),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri.clone(), edits)])),
+            ..WorkspaceEdit::default()
+        }),
+        command: None,
+        is_preferred: Some(is_preferred),
+        disabled: None,
+        data: None,
+    })
+}
+
+/// Import a module that exports a symbol
+/// with the name of the unresolved reference.
+fn import_fixes<E: Environment>(
+    ws: &Workspace<E>,
+    doc: &Document,
+    uri: &Url,
+    source: Source,
+    reference: Symbol,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeActionOrCommand> {
+    let hir = &ws.hir;
+
+    // The diagnostic might be outdated.
+    let Some(name) = hir
+        .symbol(reference)
+        .filter(|data| data.source.is(source))
+        .and_then(|data| data.name(hir))
+    else {
+        return Vec::new();
+    };
+
+    let current_module = hir.module_by_source(source);
+
+    hir.modules()
+        .filter(|&(module, _)| Some(module) != current_module)
+        .filter(|(_, data)| {
+            hir.scope_symbols(data.scope).any(|sym| {
+                let sym_data = &hir[sym];
+                sym_data.export
+                    && (sym_data.kind.is_fn() || sym_data.kind.is_decl())
+                    && sym_data.name(hir) == Some(name)
+            })
+        })
+        .filter_map(|(module, data)| {
+            let module_url = data.url()?;
+
+            let mut edits = Vec::new();
+
+            let alias = match existing_import_alias(ws, source, module) {
+                Some(alias) => alias,
+                None => {
+                    let path = import_path(uri, module_url)?;
+                    let alias = module_url
+                        .path_segments()?
+                        .next_back()?
+                        .trim_end_matches(".rhai")
+                        .to_string();
+
+                    if !is_valid_ident(&alias) {
+                        return None;
+                    }
+
+                    edits.push(import_edit(doc, &path, &alias)?);
+                    alias
+                }
+            };
+
+            edits.push(TextEdit {
+                range: diagnostic.range,
+                new_text: format!("{alias}::{name}"),
+            });
+
+            Some(quick_fix(
+                format!("Import `{name}` from `{alias}`"),
+                uri,
+                edits,
+                diagnostic,
+                false,
+            ))
+        })
+        .collect()
+}
+
+/// The alias of an import of the given module in the source.
+fn existing_import_alias<E: Environment>(
+    ws: &Workspace<E>,
+    source: Source,
+    module: Module,
+) -> Option<String> {
+    let hir = &ws.hir;
+
+    hir.symbols().find_map(|(_, data)| {
+        let import = data.kind.as_import()?;
+
+        if !data.source.is(source) || import.target != Some(module) {
+            return None;
+        }
+
+        Some(hir[import.alias?].name(hir)?.to_string())
+    })
+}
+
+/// A relative import path from the document to the module.
+fn import_path(from: &Url, to: &Url) -> Option<String> {
+    let path = from.make_relative(to)?;
+    let path = path.trim_end_matches(".rhai");
+
+    if path.starts_with("../") {
+        Some(path.to_string())
+    } else {
+        Some(format!("./{path}"))
+    }
+}
+
+/// Insert an import statement after the existing imports,
+/// or at the start of the document.
+fn import_edit(doc: &Document, path: &str, alias: &str) -> Option<TextEdit> {
+    let rhai = Rhai::cast(doc.parse().clone_syntax())?;
+
+    let last_import = rhai
+        .statements()
+        .filter(|stmt| {
+            matches!(
+                stmt.item().and_then(|item| item.expr()),
+                Some(Expr::Import(_))
+            )
+        })
+        .last();
+
+    let import = format!("import {path:?} as {alias};");
+
+    let (offset, new_text) = match (last_import, rhai.shebang_token()) {
+        (Some(stmt), _) => (stmt.syntax().text_range().end(), format!("\n{import}")),
+        (None, Some(shebang)) => (shebang.text_range().end(), format!("\n{import}\n")),
+        (None, None) => (TextSize::from(0), format!("{import}\n\n")),
+    };
+
+    Some(TextEdit {
+        range: doc.mapper.range(TextRange::empty(offset))?.into_lsp(),
+        new_text,
+    })
+}
+
+/// Move a nested function after the top-level
+/// statement it is part of.
+fn move_to_top_level_fix<E: Environment>(
+    ws: &Workspace<E>,
+    doc: &Document,
+    uri: &Url,
+    source: Source,
+    function: Symbol,
+    diagnostic: &Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    let fn_range = ws
+        .hir
+        .symbol(function)
+        .filter(|data| data.source.is(source) && data.kind.is_fn())?
+        .text_range()?;
+
+    let root = doc.parse().clone_syntax();
+
+    let fn_stmt = root
+        .covering_element(fn_range)
+        .ancestors()
+        .find(|node| node.kind() == STMT)?;
+
+    let top_level_stmt = fn_stmt
+        .ancestors()
+        .filter(|node| node.kind() == STMT)
+        .last()?;
+
+    if top_level_stmt == fn_stmt {
+        return None;
+    }
+
+    // Statements include the whitespace after them.
+    let fn_range = trimmed_range(&fn_stmt)?;
+    let top_level_end = trimmed_range(&top_level_stmt)?.end();
+
+    // Also remove the indentation before the function.
+    let remove_start = fn_stmt
+        .prev_sibling_or_token()
+        .filter(|prev| prev.kind() == WHITESPACE)
+        .map_or(fn_range.start(), |ws| ws.text_range().start());
+
+    let root_text = root.text().to_string();
+    let fn_text = dedent(
+        &root_text[fn_range],
+        &line_indent(&root_text, fn_range.start()),
+    );
+
+    Some(quick_fix(
+        "Move function to the top level".into(),
+        uri,
+        Vec::from([
+            TextEdit {
+                range: doc
+                    .mapper
+                    .range(TextRange::new(remove_start, fn_range.end()))?
+                    .into_lsp(),
+                new_text: String::new(),
+            },
+            TextEdit {
+                range: doc
+                    .mapper
+                    .range(TextRange::empty(top_level_end))?
+                    .into_lsp(),
+                new_text: format!("\n\n{fn_text}"),
+            },
+        ]),
+        diagnostic,
+        true,
+    ))
+}
+
+/// The whitespace at the start of the line of the offset.
+fn line_indent(text: &str, offset: TextSize) -> String {
+    let line_start = text[..usize::from(offset)]
+        .rfind('\n')
+        .map_or(0, |idx| idx + 1);
+
+    text[line_start..usize::from(offset)]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
+fn dedent(text: &str, indent: &str) -> String {
+    text.lines()
+        .map(|line| line.strip_prefix(indent).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Position, Range};
+    use rhai_common::environment::native::NativeEnvironment;
+
+    fn workspace(files: &[(&str, &str)]) -> Workspace<NativeEnvironment> {
+        let mut ws = Workspace::new(NativeEnvironment, "file:///root/".parse().unwrap());
+
+        for (path, text) in files {
+            ws.add_document(format!("file:///root/{path}").parse().unwrap(), text);
+        }
+
+        ws.hir.resolve_all();
+        ws
+    }
+
+    /// The symbol of the first error in the document that `f` accepts.
+    fn error_symbol(
+        ws: &Workspace<NativeEnvironment>,
+        uri: &Url,
+        f: impl Fn(&ErrorKind) -> Option<Symbol>,
+    ) -> (Source, Symbol) {
+        let source = ws.hir.source_of(uri).unwrap();
+        let symbol = ws
+            .hir
+            .errors_for_source(source)
+            .into_iter()
+            .find_map(|error| f(&error.kind))
+            .unwrap();
+
+        (source, symbol)
+    }
+
+    fn unresolved_reference(kind: &ErrorKind) -> Option<Symbol> {
+        match kind {
+            ErrorKind::UnresolvedReference {
+                reference_symbol, ..
+            } => Some(*reference_symbol),
+            _ => None,
+        }
+    }
+
+    fn edits(action: &CodeActionOrCommand) -> Vec<(Range, String)> {
+        let CodeActionOrCommand::CodeAction(action) = action else {
+            panic!("expected a code action");
+        };
+
+        action
+            .edit
+            .as_ref()
+            .and_then(|edit| edit.changes.as_ref())
+            .unwrap()
+            .values()
+            .flatten()
+            .map(|edit| (edit.range, edit.new_text.clone()))
+            .collect()
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+    }
+
+    #[test]
+    fn test_import_fix() {
+        let ws = workspace(&[
+            ("lib.rhai", "fn helper() {}\n"),
+            ("main.rhai", "helper();\n"),
+        ]);
+        let uri: Url = "file:///root/main.rhai".parse().unwrap();
+        let (source, reference) = error_symbol(&ws, &uri, unresolved_reference);
+
+        let diagnostic = Diagnostic {
+            range: range((0, 0), (0, 6)),
+            ..Diagnostic::default()
+        };
+
+        let doc = ws.document(&uri).unwrap();
+        let fixes = import_fixes(&ws, doc, &uri, source, reference, &diagnostic);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(
+            edits(&fixes[0]),
+            [
+                (range((0, 0), (0, 0)), "import \"./lib\" as lib;\n\n".into()),
+                (diagnostic.range, "lib::helper".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_fix_uses_existing_import() {
+        let ws = workspace(&[
+            ("lib.rhai", "fn helper() {}\nfn other() {}\n"),
+            (
+                "main.rhai",
+                "import \"./lib\" as l;\n\nl::other();\nhelper();\n",
+            ),
+        ]);
+        let uri: Url = "file:///root/main.rhai".parse().unwrap();
+        let (source, reference) = error_symbol(&ws, &uri, unresolved_reference);
+
+        let diagnostic = Diagnostic {
+            range: range((3, 0), (3, 6)),
+            ..Diagnostic::default()
+        };
+
+        let doc = ws.document(&uri).unwrap();
+        let fixes = import_fixes(&ws, doc, &uri, source, reference, &diagnostic);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(edits(&fixes[0]), [(diagnostic.range, "l::helper".into())]);
+    }
+
+    #[test]
+    fn test_move_to_top_level_fix() {
+        let ws = workspace(&[(
+            "main.rhai",
+            "fn outer() {\n    fn inner() {\n        1\n    }\n    inner()\n}\n",
+        )]);
+        let uri: Url = "file:///root/main.rhai".parse().unwrap();
+        let (source, function) = error_symbol(&ws, &uri, |kind| match kind {
+            ErrorKind::NestedFunction { function } => Some(*function),
+            _ => None,
+        });
+
+        let doc = ws.document(&uri).unwrap();
+        let fix = move_to_top_level_fix(&ws, doc, &uri, source, function, &Diagnostic::default())
+            .unwrap();
+
+        assert_eq!(
+            edits(&fix),
+            [
+                (range((0, 12), (3, 5)), String::new()),
+                (range((5, 1), (5, 1)), "\n\nfn inner() {\n    1\n}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_path() {
+        let from: Url = "file:///root/src/main.rhai".parse().unwrap();
+
+        assert_eq!(
+            import_path(&from, &"file:///root/src/lib.rhai".parse().unwrap()).unwrap(),
+            "./lib"
+        );
+        assert_eq!(
+            import_path(&from, &"file:///root/util/lib.rhai".parse().unwrap()).unwrap(),
+            "../util/lib"
+        );
+    }
+}
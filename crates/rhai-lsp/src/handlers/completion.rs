@@ -36,7 +36,7 @@ pub(crate) async fn completion<E: Environment>(
 
     let doc = ws.document(&uri)?;
 
-    let syntax = doc.parse.clone().into_syntax();
+    let syntax = doc.parse().clone().into_syntax();
 
     let offset = match doc.mapper.offset(Position::from_lsp(pos)) {
         Some(p) => p,
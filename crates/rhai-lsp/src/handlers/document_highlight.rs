@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use crate::world::World;
+use lsp_async_stub::{rpc, util::LspExt, Context, Params};
+use lsp_types::{DocumentHighlight, DocumentHighlightKind, DocumentHighlightParams};
+use rhai_common::{environment::Environment, util::Normalize};
+use rhai_hir::{
+    source::Source,
+    symbol::{ReferenceTarget, SymbolKind},
+    Hir, Symbol,
+};
+use rhai_rowan::syntax::SyntaxKind;
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn document_highlight<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<DocumentHighlightParams>,
+) -> Result<Option<Vec<DocumentHighlight>>, rpc::Error> {
+    let p = params.required()?;
+
+    let uri = p.text_document_position_params.text_document.uri;
+    let pos = p.text_document_position_params.position;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&uri);
+
+    let doc = ws.document(&uri)?;
+
+    let Some(offset) = doc
+        .mapper
+        .offset(lsp_async_stub::util::Position::from_lsp(pos))
+    else {
+        return Ok(None);
+    };
+
+    let Some(source) = ws.hir.source_of(&uri.clone().normalize()) else {
+        return Ok(None);
+    };
+
+    let Some(symbol) = ws.hir.symbol_selection_at(source, offset, true) else {
+        return Ok(None);
+    };
+
+    let target = match &ws.hir[symbol].kind {
+        SymbolKind::Ref(r) => match r.target {
+            Some(ReferenceTarget::Symbol(target)) => target,
+            _ => return Ok(None),
+        },
+        _ => symbol,
+    };
+
+    let references = match &ws.hir[target].kind {
+        SymbolKind::Fn(f) => &f.references,
+        SymbolKind::Decl(d) => &d.references,
+        _ => return Ok(None),
+    };
+
+    let assigned = assigned_references(&ws.hir, source);
+
+    let declaration = Some(target)
+        .filter(|&target| ws.hir[target].source.is(source))
+        .map(|target| (target, DocumentHighlightKind::WRITE));
+
+    let references = references
+        .iter()
+        .filter(|&&reference| ws.hir[reference].source.is(source))
+        .map(|&reference| {
+            if assigned.contains(&reference) {
+                (reference, DocumentHighlightKind::WRITE)
+            } else {
+                (reference, DocumentHighlightKind::READ)
+            }
+        });
+
+    Ok(Some(
+        declaration
+            .into_iter()
+            .chain(references)
+            .filter_map(|(symbol, kind)| {
+                Some(DocumentHighlight {
+                    range: doc
+                        .mapper
+                        .range(ws.hir[symbol].selection_or_text_range()?)?
+                        .into_lsp(),
+                    kind: Some(kind),
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// References that are assigned to with `=` or
+/// a compound assignment operator such as `+=`.
+fn assigned_references(hir: &Hir, source: Source) -> HashSet<Symbol> {
+    hir.symbols()
+        .filter(|(_, data)| data.source.is(source))
+        .filter_map(|(_, data)| match &data.kind {
+            SymbolKind::Binary(binary)
+                if binary
+                    .op
+                    .as_ref()
+                    .and_then(|op| op.as_regular())
+                    .is_some_and(SyntaxKind::is_assign_op) =>
+            {
+                binary.lhs
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_rowan::{parser::Parser, TextRange, TextSize};
+
+    #[test]
+    fn test_assigned_references() {
+        let src = "let a = 1;\na = 2;\na += 3;\nlet b = a == 4;\n";
+
+        let mut hir = Hir::new();
+        let url = "test:///root.rhai".parse().unwrap();
+        hir.add_source(&url, &Parser::new(src).parse_script().into_syntax());
+        hir.resolve_all();
+
+        let mut assigned = assigned_references(&hir, hir.source_of(&url).unwrap())
+            .into_iter()
+            .filter_map(|symbol| hir[symbol].text_range())
+            .map(TextRange::start)
+            .collect::<Vec<_>>();
+        assigned.sort();
+
+        assert_eq!(assigned, [TextSize::from(11), TextSize::from(18)]);
+    }
+}
@@ -26,7 +26,7 @@ pub(crate) async fn document_symbols<E: Environment>(
 
     let doc = ws.document(&p.text_document.uri)?;
 
-    let syntax = doc.parse.clone().into_syntax();
+    let syntax = doc.parse().clone().into_syntax();
 
     let source = match ws.hir.source_of(&p.text_document.uri.clone().normalize()) {
         Some(s) => s,
@@ -1,4 +1,7 @@
-use lsp_async_stub::{Context, Params};
+use lsp_async_stub::{
+    util::{LspExt, Range},
+    Context, Params,
+};
 use lsp_types::{
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
     DidSaveTextDocumentParams, Url,
@@ -38,19 +41,27 @@ pub(crate) async fn document_change<E: Environment>(
     context: Context<World<E>>,
     params: Params<DidChangeTextDocumentParams>,
 ) {
-    let mut p = match params.optional() {
-        None => return,
-        Some(p) => p,
+    let Some(p) = params.optional() else {
+        return;
     };
 
-    // We expect one full change
-    let change = match p.content_changes.pop() {
-        None => return,
-        Some(c) => c,
-    };
+    let uri = p.text_document.uri;
 
-    update_document(context.clone(), p.text_document.uri.clone(), &change.text).await;
-    publish_diagnostics(context.clone(), p.text_document.uri).await;
+    {
+        let mut workspaces = context.workspaces.write().await;
+        let ws = workspaces.by_document_mut(&uri);
+
+        for change in p.content_changes {
+            match change.range {
+                Some(range) => ws.edit_document(&uri, Range::from_lsp(range), &change.text),
+                None => ws.add_document(uri.clone(), &change.text),
+            }
+        }
+
+        ws.hir.resolve_changed();
+    }
+
+    publish_diagnostics(context.clone(), uri).await;
 
     context
         .clone()
@@ -79,5 +90,5 @@ pub(crate) async fn update_document<E: Environment>(ctx: Context<World<E>>, uri:
     let mut ws = ctx.workspaces.write().await;
     let ws = ws.by_document_mut(&uri);
     ws.add_document(uri, text);
-    ws.hir.resolve_all();
+    ws.hir.resolve_changed();
 }
@@ -22,7 +22,7 @@ pub(crate) async fn folding_ranges<E: Environment>(
 
     let doc = ws.document(&p.text_document.uri)?;
 
-    let syntax = doc.parse.clone().into_syntax();
+    let syntax = doc.parse().clone().into_syntax();
 
     Ok(Some(
         syntax
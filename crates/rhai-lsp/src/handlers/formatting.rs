@@ -1,7 +1,15 @@
-use crate::world::World;
-use lsp_async_stub::{rpc, util::LspExt, Context, Params};
-use lsp_types::{DocumentFormattingParams, TextEdit};
+use crate::world::{Document, Workspace, World};
+use lsp_async_stub::{
+    rpc,
+    util::{LspExt, Position},
+    Context, Params,
+};
+use lsp_types::{
+    DocumentFormattingParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
+    FormattingOptions, TextEdit,
+};
 use rhai_common::environment::Environment;
+use rhai_rowan::{TextRange, TextSize};
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn format<E: Environment>(
@@ -20,17 +28,121 @@ pub(crate) async fn format<E: Environment>(
         }
     };
 
-    let format_opts = rhai_fmt::Options {
-        indent_string: if p.options.insert_spaces {
-            " ".repeat(p.options.tab_size as usize)
-        } else {
-            "\t".into()
-        },
-        ..Default::default()
+    Ok(Some(
+        rhai_fmt::format_edits(&doc.parse().clone_syntax(), format_options(ws, &p.options))
+            .into_iter()
+            .filter_map(|edit| lsp_edit(doc, edit))
+            .collect(),
+    ))
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn format_range<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<DocumentRangeFormattingParams>,
+) -> Result<Option<Vec<TextEdit>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&p.text_document.uri);
+    let doc = match ws.document(&p.text_document.uri) {
+        Ok(d) => d,
+        Err(error) => {
+            tracing::debug!(%error, "failed to get document from workspace");
+            return Ok(None);
+        }
+    };
+
+    let Some(range) = doc
+        .mapper
+        .text_range(lsp_async_stub::util::Range::from_lsp(p.range))
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(format_text_range(
+        doc,
+        range,
+        format_options(ws, &p.options),
+    )))
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn format_on_type<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<DocumentOnTypeFormattingParams>,
+) -> Result<Option<Vec<TextEdit>>, rpc::Error> {
+    let p = params.required()?;
+
+    let uri = p.text_document_position.text_document.uri;
+    let pos = p.text_document_position.position;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&uri);
+    let doc = match ws.document(&uri) {
+        Ok(d) => d,
+        Err(error) => {
+            tracing::debug!(%error, "failed to get document from workspace");
+            return Ok(None);
+        }
+    };
+
+    let Some(offset) = doc.mapper.offset(Position::from_lsp(pos)) else {
+        return Ok(None);
+    };
+
+    // The position is right after the typed character.
+    let Some(start) = offset.checked_sub(TextSize::of(p.ch.as_str())) else {
+        return Ok(None);
+    };
+
+    Ok(Some(format_text_range(
+        doc,
+        TextRange::new(start, offset),
+        format_options(ws, &p.options),
+    )))
+}
+
+/// Format the statements covering the range,
+/// edits that would touch syntax errors are skipped.
+fn format_text_range(
+    doc: &Document,
+    range: TextRange,
+    options: rhai_fmt::Options,
+) -> Vec<TextEdit> {
+    rhai_fmt::format_range(&doc.parse().clone_syntax(), range, options)
+        .into_iter()
+        .filter(|edit| {
+            !doc.parse()
+                .errors
+                .iter()
+                .any(|error| edit.range.intersect(error.range).is_some())
+        })
+        .filter_map(|edit| lsp_edit(doc, edit))
+        .collect()
+}
+
+fn lsp_edit(doc: &Document, edit: rhai_fmt::TextEdit) -> Option<TextEdit> {
+    Some(TextEdit {
+        range: doc.mapper.range(edit.range)?.into_lsp(),
+        new_text: edit.new_text,
+    })
+}
+
+/// Options from the workspace configuration,
+/// the indentation is always taken from the editor.
+fn format_options<E: Environment>(
+    ws: &Workspace<E>,
+    options: &FormattingOptions,
+) -> rhai_fmt::Options {
+    let mut format_opts = rhai_fmt::Options::default();
+    format_opts.update(ws.rhai_config.fmt.options.clone());
+
+    format_opts.indent_string = if options.insert_spaces {
+        " ".repeat(options.tab_size as usize)
+    } else {
+        "\t".into()
     };
 
-    Ok(Some(vec![TextEdit {
-        range: doc.mapper.all_range().into_lsp(),
-        new_text: rhai_fmt::format_syntax(doc.parse.clone_syntax(), format_opts),
-    }]))
+    format_opts
 }
@@ -32,7 +32,7 @@ pub(crate) async fn hover<E: Environment>(
         None => return Ok(None),
     };
 
-    let syntax = doc.parse.clone_syntax();
+    let syntax = doc.parse().clone_syntax();
 
     let query = Query::at(&syntax, offset);
 
@@ -60,7 +60,7 @@ pub(crate) async fn hover<E: Environment>(
 
         return Ok(hover_for_symbol(
             &ws.hir,
-            &doc.parse.clone_syntax(),
+            &doc.parse().clone_syntax(),
             highlight_range,
             symbol,
         ));
@@ -7,11 +7,14 @@ use crate::{
 };
 use lsp_async_stub::{rpc::Error, Context, Params};
 use lsp_types::{
-    CompletionOptions, DeclarationCapability, FoldingRangeProviderCapability,
-    HoverProviderCapability, InitializedParams, OneOf, RenameOptions, SemanticTokensFullOptions,
+    CallHierarchyServerCapability, CodeActionKind, CodeActionOptions, CodeActionProviderCapability,
+    CompletionOptions, DeclarationCapability, DocumentOnTypeFormattingOptions,
+    FoldingRangeProviderCapability, HoverProviderCapability, InitializedParams, OneOf,
+    RenameOptions, SelectionRangeProviderCapability, SemanticTokensFullOptions,
     SemanticTokensLegend, SemanticTokensOptions, SemanticTokensServerCapabilities,
-    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind,
-    WorkDoneProgressOptions, WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
+    ServerCapabilities, ServerInfo, SignatureHelpOptions, TextDocumentSyncCapability,
+    TextDocumentSyncKind, WorkDoneProgressOptions, WorkspaceFoldersServerCapabilities,
+    WorkspaceServerCapabilities,
 };
 use lsp_types::{InitializeParams, InitializeResult};
 use rhai_common::environment::Environment;
@@ -51,18 +54,29 @@ pub async fn initialize<E: Environment>(
                 }),
                 ..Default::default()
             }),
-            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                TextDocumentSyncKind::INCREMENTAL,
+            )),
             rename_provider: Some(OneOf::Right(RenameOptions {
                 prepare_provider: Some(true),
                 work_done_progress_options: Default::default(),
             })),
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
             references_provider: Some(OneOf::Left(true)),
+            document_highlight_provider: Some(OneOf::Left(true)),
             declaration_provider: Some(DeclarationCapability::Simple(true)),
             definition_provider: Some(OneOf::Left(true)),
             document_symbol_provider: Some(OneOf::Left(true)),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
+            call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
             hover_provider: Some(HoverProviderCapability::Simple(true)),
             document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                first_trigger_character: "}".into(),
+                more_trigger_character: Some(vec![";".into()]),
+            }),
             semantic_tokens_provider: Some(
                 SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
                     work_done_progress_options: WorkDoneProgressOptions {
@@ -81,12 +95,24 @@ pub async fn initialize<E: Environment>(
                 trigger_characters: Some(vec!["#".into(), "=".into(), ".".into(), ":".into()]),
                 ..CompletionOptions::default()
             }),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+                resolve_provider: None,
+            })),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".into(), ",".into()]),
+                retrigger_characters: None,
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
             ..Default::default()
         },
         server_info: Some(ServerInfo {
             name: "Rhai Language Server".into(),
             version: Some(env!("CARGO_PKG_VERSION").into()),
         }),
+        offset_encoding: None,
     })
 }
 
@@ -97,6 +123,7 @@ pub async fn initialized<E: Environment>(
 ) {
     update_configuration(context.clone()).await;
 
+    let init_config = context.init_config.load();
     let mut workspaces = context.workspaces.write().await;
 
     for (ws_url, ws) in workspaces.iter_mut() {
@@ -108,7 +135,7 @@ pub async fn initialized<E: Environment>(
             tracing::error!(%error, "failed to load Rhai config");
         }
 
-        ws.load_all_files().await;
+        ws.load_all_files(init_config.cache_path.as_deref()).await;
     }
 
     drop(workspaces);
@@ -0,0 +1,285 @@
+use crate::{config::InlayHintsConfig, world::World};
+use lsp_async_stub::{
+    rpc,
+    util::{LspExt, Mapper, Range},
+    Context, Params,
+};
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams};
+use rhai_common::{environment::Environment, util::Normalize};
+use rhai_hir::{
+    source::Source,
+    symbol::{CallSymbol, DeclSymbol, FnSymbol, ReferenceTarget, SymbolKind},
+    ty::Type,
+    Hir, Symbol,
+};
+use rhai_rowan::{
+    ast::{AstNode, ExprFn},
+    syntax::SyntaxNode,
+    TextRange, TextSize,
+};
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn inlay_hints<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<InlayHintParams>,
+) -> Result<Option<Vec<InlayHint>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&p.text_document.uri);
+
+    let doc = ws.document(&p.text_document.uri)?;
+
+    let Some(source) = ws.hir.source_of(&p.text_document.uri.clone().normalize()) else {
+        return Ok(None);
+    };
+
+    let Some(range) = doc.mapper.text_range(Range::from_lsp(p.range)) else {
+        return Ok(None);
+    };
+
+    let hints = collect_hints(
+        &ws.hir,
+        source,
+        &doc.parse().clone_syntax(),
+        &ws.config.inlay_hints,
+        range,
+    );
+
+    Ok(Some(
+        hints
+            .into_iter()
+            .filter_map(|(offset, hint)| hint.into_lsp(&doc.mapper, offset))
+            .collect(),
+    ))
+}
+
+/// The hints in the range of the source ordered by their offsets.
+fn collect_hints(
+    hir: &Hir,
+    source: Source,
+    syntax: &SyntaxNode,
+    config: &InlayHintsConfig,
+    range: TextRange,
+) -> Vec<(TextSize, Hint)> {
+    let mut hints = Vec::new();
+
+    for (symbol, data) in hir.symbols() {
+        if !data.source.is(source)
+            || data
+                .text_range()
+                .is_none_or(|r| r.intersect(range).is_none())
+        {
+            continue;
+        }
+
+        match &data.kind {
+            SymbolKind::Decl(decl) if config.variable_types => {
+                hints.extend(variable_type_hint(hir, symbol, decl));
+            }
+            SymbolKind::Call(call) if config.parameter_names => {
+                hints.extend(parameter_name_hints(hir, call));
+            }
+            SymbolKind::Fn(f) if config.return_types => {
+                hints.extend(return_type_hint(hir, syntax, symbol, f));
+            }
+            _ => {}
+        }
+    }
+
+    hints.retain(|(offset, _)| range.contains_inclusive(*offset));
+    hints.sort_by_key(|(offset, _)| *offset);
+    hints
+}
+
+/// An inlay hint that is not yet positioned in the document.
+struct Hint {
+    label: String,
+    kind: InlayHintKind,
+}
+
+impl Hint {
+    fn into_lsp(self, mapper: &Mapper, offset: TextSize) -> Option<InlayHint> {
+        Some(InlayHint {
+            position: mapper.position(offset)?.into_lsp(),
+            label: InlayHintLabel::String(self.label),
+            kind: Some(self.kind),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(self.kind == InlayHintKind::PARAMETER),
+            data: None,
+        })
+    }
+}
+
+fn variable_type_hint(hir: &Hir, symbol: Symbol, decl: &DeclSymbol) -> Option<(TextSize, Hint)> {
+    if decl.is_param
+        || decl.is_const
+        || decl.is_pat
+        || decl.is_import
+        || decl.ty_decl.is_some()
+        || decl.value.is_none()
+    {
+        return None;
+    }
+
+    let ty = hir[symbol].ty;
+
+    if !is_displayed_type(hir, ty) {
+        return None;
+    }
+
+    Some((
+        hir[symbol].selection_range()?.end(),
+        Hint {
+            label: format!(": {}", ty.fmt(hir)),
+            kind: InlayHintKind::TYPE,
+        },
+    ))
+}
+
+fn parameter_name_hints(hir: &Hir, call: &CallSymbol) -> Vec<(TextSize, Hint)> {
+    let Some(ReferenceTarget::Symbol(function)) = hir
+        .call_reference(call)
+        .and_then(|reference| hir[reference].target())
+    else {
+        return Vec::new();
+    };
+
+    let Some(f) = hir[function].kind.as_fn().filter(|f| f.is_def) else {
+        return Vec::new();
+    };
+
+    call.arguments
+        .iter()
+        .zip(&f.params)
+        .filter_map(|(&arg, &param)| {
+            let param = hir[param].kind.as_decl()?;
+
+            // The name of a variable passed as the
+            // argument is usually descriptive enough.
+            if param.is_rest || hir[arg].name(hir) == Some(param.name.as_str()) {
+                return None;
+            }
+
+            Some((
+                hir[arg].text_range()?.start(),
+                Hint {
+                    label: format!("{}:", param.name),
+                    kind: InlayHintKind::PARAMETER,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn return_type_hint(
+    hir: &Hir,
+    root: &SyntaxNode,
+    symbol: Symbol,
+    f: &FnSymbol,
+) -> Option<(TextSize, Hint)> {
+    if f.is_def {
+        return None;
+    }
+
+    let ret = hir[hir[symbol].ty].kind.as_fn()?.ret;
+
+    if !is_displayed_type(hir, ret) {
+        return None;
+    }
+
+    let fn_range: TextRange = hir[symbol].text_range()?;
+
+    let param_list = root
+        .descendants()
+        .filter_map(ExprFn::cast)
+        .find(|expr| expr.syntax().text_range() == fn_range)?
+        .param_list()?;
+
+    Some((
+        param_list.syntax().text_range().end(),
+        Hint {
+            label: format!(" -> {}", ret.fmt(hir)),
+            kind: InlayHintKind::TYPE,
+        },
+    ))
+}
+
+/// Unknown types carry no information,
+/// and nothing is usually written for void.
+fn is_displayed_type(hir: &Hir, ty: Type) -> bool {
+    let kind = &hir[ty].kind;
+    !kind.is_unknown() && !kind.is_void()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_rowan::parser::Parser;
+
+    const DEFS: &str = r"
+module static;
+
+fn add(a: int, b: int) -> int;
+";
+
+    /// The labels of the hints in the script by their offsets.
+    fn hints(src: &str, config: &InlayHintsConfig) -> Vec<(usize, String)> {
+        let mut hir = Hir::new();
+        hir.add_source(
+            &"test:///static.d.rhai".parse().unwrap(),
+            &Parser::new(DEFS).parse_def().into_syntax(),
+        );
+
+        let url = "test:///root.rhai".parse().unwrap();
+        let syntax = Parser::new(src).parse_script().into_syntax();
+        hir.add_source(&url, &syntax);
+        hir.resolve_all();
+
+        let source = hir.source_of(&url).unwrap();
+
+        collect_hints(&hir, source, &syntax, config, syntax.text_range())
+            .into_iter()
+            .map(|(offset, hint)| (usize::from(offset), hint.label))
+            .collect()
+    }
+
+    const SRC: &str = r#"let x = 1;
+let a = x;
+let unknown;
+let sum = add(a, 2);
+fn foo() { "foo" }
+"#;
+
+    #[test]
+    fn test_hints() {
+        let end_of = |s: &str| SRC.find(s).unwrap() + s.len();
+
+        assert_eq!(
+            hints(SRC, &InlayHintsConfig::default()),
+            [
+                (end_of("let x"), ": int".into()),
+                (end_of("let a"), ": int".into()),
+                (end_of("let sum"), ": int".into()),
+                (end_of("add(a, "), "b:".into()),
+                (end_of("fn foo()"), " -> String".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled_hints() {
+        let config = InlayHintsConfig {
+            variable_types: false,
+            parameter_names: true,
+            return_types: false,
+        };
+
+        assert_eq!(
+            hints(SRC, &config),
+            [(SRC.find("2);").unwrap(), "b:".into())]
+        );
+    }
+}
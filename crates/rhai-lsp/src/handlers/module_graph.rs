@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use crate::{
+    lsp_ext::request::{
+        ModuleGraphImport, ModuleGraphModule, ModuleGraphParams, ModuleGraphResult,
+    },
+    world::{Workspace, World},
+    IndexMap,
+};
+use lsp_async_stub::{rpc, util::LspExt, Context, Params};
+use lsp_types::Location;
+use rhai_common::environment::Environment;
+use rhai_hir::{module::ModuleKind, Module, Symbol};
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn module_graph<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<ModuleGraphParams>,
+) -> Result<Option<ModuleGraphResult>, rpc::Error> {
+    let p = params.required()?;
+    let workspaces = context.workspaces.read().await;
+    let ws = if let Some(uri) = p.workspace_uri {
+        match workspaces.get(&uri) {
+            Some(w) => w,
+            None => return Ok(None),
+        }
+    } else {
+        workspaces.get_detached()
+    };
+
+    let hir = &ws.hir;
+
+    let module_indices: IndexMap<Module, ModuleGraphModule> = hir
+        .modules()
+        .filter(|(_, data)| data.kind != ModuleKind::Inline)
+        .map(|(module, data)| {
+            let uri = data.sources.first().map(|&source| hir[source].url.clone());
+
+            (
+                module,
+                ModuleGraphModule {
+                    name: data.kind.to_string(),
+                    uri,
+                },
+            )
+        })
+        .collect();
+
+    let circular: HashSet<Symbol> = hir
+        .import_cycles()
+        .into_iter()
+        .map(|cycle| cycle[0].import)
+        .collect();
+
+    let imports = hir
+        .module_imports()
+        .into_iter()
+        .filter_map(|import| {
+            Some(ModuleGraphImport {
+                from: module_indices.get_index_of(&import.from)?,
+                to: module_indices.get_index_of(&import.to)?,
+                location: import_location(ws, import.import),
+                circular: circular.contains(&import.import),
+            })
+        })
+        .collect();
+
+    Ok(Some(ModuleGraphResult {
+        modules: module_indices.into_values().collect(),
+        imports,
+    }))
+}
+
+fn import_location<E: Environment>(ws: &Workspace<E>, import: Symbol) -> Option<Location> {
+    let symbol_data = &ws.hir[import];
+    let uri = &ws.hir[symbol_data.source.source?].url;
+    let doc = ws.documents.get(uri)?;
+
+    Some(Location {
+        uri: uri.clone(),
+        range: doc
+            .mapper
+            .range(symbol_data.selection_or_text_range()?)?
+            .into_lsp(),
+    })
+}
@@ -29,7 +29,7 @@ pub(crate) async fn references<E: Environment>(
     };
 
     let elem = doc
-        .parse
+        .parse()
         .clone_syntax()
         .covering_element(TextRange::new(offset, offset));
 
@@ -0,0 +1,299 @@
+use crate::world::World;
+use lsp_async_stub::{
+    rpc,
+    util::{LspExt, Position},
+    Context, Params,
+};
+use lsp_types::{SelectionRange, SelectionRangeParams};
+use rhai_common::environment::Environment;
+use rhai_rowan::{
+    syntax::{SyntaxElement, SyntaxKind::*, SyntaxNode, SyntaxToken},
+    util::trimmed_range,
+    TextRange, TextSize, TokenAtOffset,
+};
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn selection_ranges<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<SelectionRangeParams>,
+) -> Result<Option<Vec<SelectionRange>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&p.text_document.uri);
+
+    let doc = ws.document(&p.text_document.uri)?;
+
+    let syntax = doc.parse().clone().into_syntax();
+
+    Ok(Some(
+        p.positions
+            .into_iter()
+            .map(|pos| {
+                let ranges = doc
+                    .mapper
+                    .offset(Position::from_lsp(pos))
+                    .map(|offset| expanding_ranges(&syntax, offset))
+                    .unwrap_or_default();
+
+                // Every position must have a selection range,
+                // an empty range at the position itself is used as a fallback.
+                ranges
+                    .into_iter()
+                    .rev()
+                    .filter_map(|range| doc.mapper.range(range).map(LspExt::into_lsp))
+                    .fold(None, |parent, range| {
+                        Some(SelectionRange {
+                            range,
+                            parent: parent.map(Box::new),
+                        })
+                    })
+                    .unwrap_or(SelectionRange {
+                        range: lsp_types::Range::new(pos, pos),
+                        parent: None,
+                    })
+            })
+            .collect(),
+    ))
+}
+
+/// Ranges that contain the offset, starting with the innermost one.
+fn expanding_ranges(root: &SyntaxNode, offset: TextSize) -> Vec<TextRange> {
+    let token = match root.token_at_offset(offset) {
+        TokenAtOffset::None => return Vec::new(),
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            // Prefer identifiers over the punctuation that follows them.
+            if right.kind() == WHITESPACE || (left.kind() == IDENT && right.kind() != IDENT) {
+                left
+            } else {
+                right
+            }
+        }
+    };
+
+    let mut ranges = Vec::new();
+
+    match token.kind() {
+        WHITESPACE => {}
+        COMMENT_LINE | COMMENT_LINE_DOC => {
+            ranges.push(token.text_range());
+            ranges.push(comment_group(&token));
+        }
+        LIT_STR => match token.parent().filter(|p| p.kind() == LIT_STR_TEMPLATE) {
+            Some(template) => {
+                ranges.push(template_segment(&template, token.text_range()));
+            }
+            None => ranges.push(token.text_range()),
+        },
+        _ => ranges.push(token.text_range()),
+    }
+
+    for node in token.parent_ancestors() {
+        match node.kind() {
+            LIT_STR_TEMPLATE_INTERPOLATION => {
+                ranges.extend(trimmed_range(&node));
+
+                // Include the surrounding `${` and `}`.
+                let start = node
+                    .prev_sibling_or_token()
+                    .filter(|t| t.kind() == INTERPOLATION_START);
+                let end = node
+                    .next_sibling_or_token()
+                    .filter(|t| t.kind() == PUNCT_BRACE_END);
+
+                if let (Some(start), Some(end)) = (start, end) {
+                    ranges.push(start.text_range().cover(end.text_range()));
+                }
+            }
+            LIT_STR_TEMPLATE => {
+                ranges.push(template_segment(&node, node.text_range()));
+                ranges.push(node.text_range());
+            }
+            _ => ranges.extend(trimmed_range(&node)),
+        }
+    }
+
+    // Each range must contain the previous one.
+    let mut expanding: Vec<TextRange> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        if expanding
+            .last()
+            .is_none_or(|&last| range != last && range.contains_range(last))
+        {
+            expanding.push(range);
+        }
+    }
+
+    expanding
+}
+
+/// Consecutive comments of the same kind
+/// with no empty lines between them.
+fn comment_group(token: &SyntaxToken) -> TextRange {
+    let mut range = token.text_range();
+
+    let mut extend = |element: SyntaxElement| -> bool {
+        match element.kind() {
+            WHITESPACE => element
+                .as_token()
+                .is_some_and(|ws| ws.text().matches('\n').count() <= 1),
+            kind if kind == token.kind() => {
+                range = range.cover(element.text_range());
+                true
+            }
+            _ => false,
+        }
+    };
+
+    let mut prev = token.prev_sibling_or_token();
+    while let Some(element) = prev {
+        prev = element.prev_sibling_or_token();
+        if !extend(element) {
+            break;
+        }
+    }
+
+    let mut next = token.next_sibling_or_token();
+    while let Some(element) = next {
+        next = element.next_sibling_or_token();
+        if !extend(element) {
+            break;
+        }
+    }
+
+    range
+}
+
+/// The range without the backticks of the template string.
+fn template_segment(template: &SyntaxNode, range: TextRange) -> TextRange {
+    let template_range = template.text_range();
+    let backtick = TextSize::from(1);
+
+    let start = if range.start() == template_range.start() {
+        range.start() + backtick
+    } else {
+        range.start()
+    };
+
+    let end = if range.end() == template_range.end() {
+        range.end() - backtick
+    } else {
+        range.end()
+    };
+
+    if start <= end {
+        TextRange::new(start, end)
+    } else {
+        range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_rowan::{parser::Parser, util::src_cursor_offset};
+
+    /// The texts of the ranges around the cursor marked with `$$`.
+    fn expanding_texts(src: &str) -> Vec<String> {
+        let (offset, src) = src_cursor_offset(src);
+        let root = Parser::new(&src).parse_script().into_syntax();
+
+        expanding_ranges(&root, offset)
+            .into_iter()
+            .map(|range| src[range].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert_eq!(
+            expanding_texts("let a = foo + b$$ar;\n"),
+            [
+                "bar",
+                "foo + bar",
+                "let a = foo + bar",
+                "let a = foo + bar;"
+            ]
+        );
+
+        // Identifiers are preferred over the punctuation after them.
+        assert_eq!(
+            expanding_texts("let a = foo$$;\n"),
+            ["foo", "let a = foo", "let a = foo;"]
+        );
+
+        assert_eq!(expanding_texts("let a = 1;\n   $$  \n"), ["let a = 1;"]);
+    }
+
+    #[test]
+    fn test_comment_groups() {
+        assert_eq!(
+            expanding_texts("// a\n// b$$\n\n// c\nlet x = 1;\n"),
+            ["// b", "// a\n// b", "// a\n// b\n\n// c\nlet x = 1;"]
+        );
+
+        // Doc comments are not grouped with other comments.
+        assert_eq!(
+            expanding_texts("/// a\n// b$$\n// c\nlet x = 1;\n"),
+            [
+                "// b",
+                "// b\n// c",
+                "/// a\n// b\n// c\nlet x = 1",
+                "/// a\n// b\n// c\nlet x = 1;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_strings() {
+        assert_eq!(
+            expanding_texts("let s = `h$$i ${name} there`;\n"),
+            [
+                "hi ",
+                "hi ${name} there",
+                "`hi ${name} there`",
+                "let s = `hi ${name} there`",
+                "let s = `hi ${name} there`;",
+            ]
+        );
+
+        assert_eq!(
+            expanding_texts("let s = `hi ${na$$me} there`;\n"),
+            [
+                "name",
+                "${name}",
+                "hi ${name} there",
+                "`hi ${name} there`",
+                "let s = `hi ${name} there`",
+                "let s = `hi ${name} there`;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_segment() {
+        let root = Parser::new("`a${b}c`").parse_script().into_syntax();
+        let template = root
+            .descendants()
+            .find(|node| node.kind() == LIT_STR_TEMPLATE)
+            .unwrap();
+
+        let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+
+        assert_eq!(template_segment(&template, range(0, 8)), range(1, 7));
+        assert_eq!(template_segment(&template, range(0, 2)), range(1, 2));
+        assert_eq!(template_segment(&template, range(6, 8)), range(6, 7));
+        assert_eq!(template_segment(&template, range(2, 6)), range(2, 6));
+
+        // Nothing is left of the empty template without the backticks.
+        let root = Parser::new("``").parse_script().into_syntax();
+        let template = root
+            .descendants()
+            .find(|node| node.kind() == LIT_STR_TEMPLATE)
+            .unwrap();
+
+        assert_eq!(template_segment(&template, range(0, 2)), range(1, 1));
+    }
+}
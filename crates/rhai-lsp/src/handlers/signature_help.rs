@@ -0,0 +1,142 @@
+use crate::world::World;
+use lsp_async_stub::{
+    rpc,
+    util::{LspExt, Position},
+    Context, Params,
+};
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+use rhai_common::{environment::Environment, util::Normalize};
+use rhai_hir::{symbol::ReferenceTarget, Hir, Symbol};
+use rhai_rowan::{ast::AstNode, query::Query};
+use std::fmt::Write;
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn signature_help<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<SignatureHelpParams>,
+) -> Result<Option<SignatureHelp>, rpc::Error> {
+    let p = params.required()?;
+
+    let uri = p.text_document_position_params.text_document.uri;
+    let pos = p.text_document_position_params.position;
+
+    let workspaces = context.workspaces.read().await;
+    let ws = workspaces.by_document(&uri);
+
+    let doc = ws.document(&uri)?;
+
+    let Some(offset) = doc.mapper.offset(Position::from_lsp(pos)) else {
+        return Ok(None);
+    };
+
+    let Some(source) = ws.hir.source_of(&uri.clone().normalize()) else {
+        return Ok(None);
+    };
+
+    let syntax = doc.parse().clone_syntax();
+    let query = Query::at(&syntax, offset);
+
+    let Some(arg_list) = query.arg_list() else {
+        return Ok(None);
+    };
+
+    let arg_index = query.arg_index(&arg_list);
+
+    let Some(call_range) = arg_list.syntax().parent().map(|call| call.text_range()) else {
+        return Ok(None);
+    };
+
+    let call = ws.hir.symbols().find_map(|(_, data)| {
+        if data.source.is(source) && data.text_range() == Some(call_range) {
+            data.kind.as_call()
+        } else {
+            None
+        }
+    });
+
+    let function = match call
+        .and_then(|call| ws.hir.call_reference(call))
+        .and_then(|reference| ws.hir[reference].target())
+    {
+        Some(ReferenceTarget::Symbol(target)) if ws.hir[target].kind.is_fn() => target,
+        _ => return Ok(None),
+    };
+
+    let overloads = ws.hir.overloads(function).collect::<Vec<_>>();
+
+    // The call might not be complete yet, so prefer the resolved
+    // overload only if it can take the argument at the cursor.
+    let takes_arg = |overload: Symbol| {
+        ws.hir[overload]
+            .kind
+            .as_fn()
+            .is_some_and(|f| f.param_for_arg(&ws.hir, arg_index).is_some())
+    };
+
+    let active_signature = Some(function)
+        .filter(|&f| takes_arg(f))
+        .or_else(|| overloads.iter().copied().find(|&o| takes_arg(o)))
+        .and_then(|active| overloads.iter().position(|&o| o == active))
+        .unwrap_or(0);
+
+    Ok(Some(SignatureHelp {
+        signatures: overloads
+            .into_iter()
+            .map(|overload| signature_information(&ws.hir, overload, arg_index))
+            .collect(),
+        active_signature: u32::try_from(active_signature).ok(),
+        active_parameter: None,
+    }))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn signature_information(hir: &Hir, function: Symbol, arg_index: usize) -> SignatureInformation {
+    let f = hir[function].kind.as_fn().unwrap();
+    let ty = hir[function].ty;
+
+    let mut label = format!("fn {}(", f.name);
+    let mut parameters = Vec::with_capacity(f.params.len());
+
+    if let Some(ty_fn) = hir[ty].kind.as_fn() {
+        for (idx, (name, param_ty)) in ty_fn.params.iter().enumerate() {
+            if idx != 0 {
+                label += ", ";
+            }
+
+            let start = label.encode_utf16().count() as u32;
+            write!(label, "{name}: {}", param_ty.fmt(hir)).unwrap();
+            let end = label.encode_utf16().count() as u32;
+
+            parameters.push(ParameterInformation {
+                label: ParameterLabel::LabelOffsets([start, end]),
+                documentation: None,
+            });
+        }
+
+        write!(label, ") -> {}", ty_fn.ret.fmt(hir)).unwrap();
+    } else {
+        label += ")";
+    }
+
+    SignatureInformation {
+        label,
+        documentation: if f.docs.is_empty() {
+            None
+        } else {
+            Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: f.docs.clone(),
+            }))
+        },
+        parameters: Some(parameters),
+        // An index out of range means that no parameter is active.
+        active_parameter: Some(
+            f.param_for_arg(hir, arg_index)
+                .and_then(|param| f.params.iter().position(|&p| p == param))
+                .unwrap_or(f.params.len()) as u32,
+        ),
+    }
+}
@@ -15,7 +15,7 @@ pub(crate) async fn syntax_tree<E: Environment>(
 
     let doc = ws.document(&p.uri)?;
 
-    let syntax = doc.parse.clone().into_syntax();
+    let syntax = doc.parse().clone().into_syntax();
     Ok(Some(SyntaxTreeResult {
         text: format!("{:#?}", &syntax),
         tree: serde_json::to_value(&syntax).unwrap_or_default(),
@@ -54,6 +54,7 @@ pub(crate) async fn watched_file_change<E: Environment>(
             FileChangeType::DELETED => {
                 let ws = workspaces.by_document_mut(&uri);
                 ws.remove_document(&uri) ;
+                ws.hir.resolve_changed();
                 clear_diagnostics(context.clone(), uri).await;
             }
             _ => {
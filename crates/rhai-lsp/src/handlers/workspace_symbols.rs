@@ -0,0 +1,232 @@
+#![allow(deprecated)]
+
+use std::collections::HashMap;
+
+use crate::world::{Workspace, World};
+use lsp_async_stub::{rpc, util::LspExt, Context, Params};
+use lsp_types::{Location, SymbolInformation, SymbolKind, WorkspaceSymbolParams};
+use rhai_common::environment::Environment;
+use rhai_hir::{
+    module::{ModuleKind, STATIC_URL_SCHEME},
+    symbol::{SymbolKind as HirSymbolKind, VirtualSymbol},
+    ty::TypeKind,
+    Hir, Module, Symbol,
+};
+use rhai_rowan::TextRange;
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn workspace_symbols<E: Environment>(
+    context: Context<World<E>>,
+    params: Params<WorkspaceSymbolParams>,
+) -> Result<Option<Vec<SymbolInformation>>, rpc::Error> {
+    let p = params.required()?;
+
+    let workspaces = context.workspaces.read().await;
+
+    let mut matches = Vec::new();
+
+    for (_, ws) in workspaces.iter() {
+        let module_names = inline_module_names(&ws.hir);
+
+        for (symbol, symbol_data) in ws.hir.symbols() {
+            let Some((name, kind)) = symbol_name_and_kind(&ws.hir, symbol) else {
+                continue;
+            };
+
+            let Some(score) = fuzzy_score(name, &p.query) else {
+                continue;
+            };
+
+            let Some(location) = symbol_location(ws, symbol) else {
+                continue;
+            };
+
+            let container_name = ws
+                .hir
+                .scope(symbol_data.parent_scope)
+                .and_then(|_| ws.hir.module_of_scope(symbol_data.parent_scope))
+                .or_else(|| Some(ws.hir[symbol_data.source.source?].module))
+                .and_then(|module| module_name(&ws.hir, &module_names, module));
+
+            matches.push((
+                score,
+                SymbolInformation {
+                    name: name.to_string(),
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location,
+                    container_name,
+                },
+            ));
+        }
+    }
+
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        a_score.cmp(b_score).then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(Some(matches.into_iter().map(|(_, info)| info).collect()))
+}
+
+fn symbol_name_and_kind(hir: &Hir, symbol: Symbol) -> Option<(&str, SymbolKind)> {
+    match &hir[symbol].kind {
+        HirSymbolKind::Fn(f) => Some((&f.name, SymbolKind::FUNCTION)),
+        HirSymbolKind::Op(op) => Some((&op.name, SymbolKind::OPERATOR)),
+        HirSymbolKind::Decl(decl) if !decl.is_param && !decl.is_pat && !decl.is_import => {
+            if decl.is_const {
+                Some((&decl.name, SymbolKind::CONSTANT))
+            } else {
+                Some((&decl.name, SymbolKind::VARIABLE))
+            }
+        }
+        HirSymbolKind::TypeDecl(ty_decl) => match &hir[ty_decl.ty].kind {
+            TypeKind::Alias(name, _) => Some((name.trim(), SymbolKind::TYPE_PARAMETER)),
+            _ => None,
+        },
+        HirSymbolKind::Virtual(VirtualSymbol::Module(m)) => Some((&m.name, SymbolKind::MODULE)),
+        _ => None,
+    }
+}
+
+/// Virtual module symbols without a source of their own
+/// point to the beginning of the first source of the module.
+fn symbol_location<E: Environment>(ws: &Workspace<E>, symbol: Symbol) -> Option<Location> {
+    let hir = &ws.hir;
+    let symbol_data = &hir[symbol];
+
+    let (source, range) = match (
+        symbol_data.source.source,
+        symbol_data.selection_or_text_range(),
+    ) {
+        (Some(source), Some(range)) => (source, range),
+        _ => match &symbol_data.kind {
+            HirSymbolKind::Virtual(VirtualSymbol::Module(m)) => (
+                *hir.module(m.module)?.sources.first()?,
+                TextRange::default(),
+            ),
+            _ => return None,
+        },
+    };
+
+    let url = &hir[source].url;
+    let doc = ws.documents.get(url)?;
+
+    Some(Location {
+        uri: url.clone(),
+        range: doc.mapper.range(range)?.into_lsp(),
+    })
+}
+
+/// Inline modules are named after the virtual module symbols
+/// that point to them.
+fn inline_module_names(hir: &Hir) -> HashMap<Module, &str> {
+    hir.symbols()
+        .filter_map(|(_, symbol_data)| match &symbol_data.kind {
+            HirSymbolKind::Virtual(VirtualSymbol::Module(m)) => Some((m.module, m.name.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn module_name(hir: &Hir, inline_names: &HashMap<Module, &str>, module: Module) -> Option<String> {
+    match &hir.module(module)?.kind {
+        ModuleKind::Static => Some("static".into()),
+        ModuleKind::Inline => inline_names.get(&module).map(|name| (*name).to_string()),
+        ModuleKind::Url(url) => {
+            if url.scheme() == STATIC_URL_SCHEME {
+                return url.host_str().map(String::from);
+            }
+
+            let file_name = url.path_segments()?.next_back()?;
+
+            Some(
+                file_name
+                    .strip_suffix(".d.rhai")
+                    .or_else(|| file_name.strip_suffix(".rhai"))
+                    .unwrap_or(file_name)
+                    .to_string(),
+            )
+        }
+    }
+}
+
+/// Case-insensitive subsequence matching,
+/// lower scores are better matches.
+///
+/// Prefix matches come first, then matches
+/// with fewer gaps between the matched characters.
+fn fuzzy_score(name: &str, query: &str) -> Option<(bool, usize, usize)> {
+    let name_lower = name.to_lowercase();
+    let query_lower = query.to_lowercase();
+
+    let mut gaps = 0;
+    let mut last_match: Option<usize> = None;
+    let mut name_chars = name_lower.chars().enumerate();
+
+    for query_char in query_lower.chars() {
+        let (idx, _) = name_chars.find(|(_, c)| *c == query_char)?;
+
+        if let Some(last) = last_match {
+            gaps += idx - last - 1;
+        }
+
+        last_match = Some(idx);
+    }
+
+    Some((
+        !name_lower.starts_with(&query_lower),
+        gaps,
+        name.chars().count(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai_rowan::parser::Parser;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("foo", "bar"), None);
+        assert_eq!(fuzzy_score("foo", "oof"), None);
+        assert_eq!(fuzzy_score("foo", "fooo"), None);
+
+        assert_eq!(fuzzy_score("foo", ""), Some((false, 0, 3)));
+        assert_eq!(fuzzy_score("FooBar", "foob"), Some((false, 0, 6)));
+        assert_eq!(fuzzy_score("FooBar", "fb"), Some((true, 2, 6)));
+        assert_eq!(fuzzy_score("foo_bar", "bar"), Some((true, 0, 7)));
+
+        // Prefixes first, then fewer gaps, then shorter names.
+        let mut names = ["xfoo", "f_o_o", "foo_long", "foo", "fxoo"];
+        names.sort_by_key(|name| fuzzy_score(name, "foo").unwrap());
+        assert_eq!(names, ["foo", "foo_long", "xfoo", "fxoo", "f_o_o"]);
+    }
+
+    #[test]
+    fn test_module_name() {
+        let mut hir = Hir::new();
+
+        hir.add_source(
+            &"file:///root/lib.rhai".parse().unwrap(),
+            &Parser::new("let a = 1;").parse_script().into_syntax(),
+        );
+        hir.add_source(
+            &"file:///root/crypto.d.rhai".parse().unwrap(),
+            &Parser::new("module crypto;\n\nmodule hash {\n    fn sha();\n}\n")
+                .parse_def()
+                .into_syntax(),
+        );
+        hir.resolve_all();
+
+        let inline_names = inline_module_names(&hir);
+
+        let mut names = hir
+            .modules()
+            .filter_map(|(module, _)| module_name(&hir, &inline_names, module))
+            .collect::<Vec<_>>();
+        names.sort();
+
+        assert_eq!(names, ["crypto", "hash", "lib", "static"]);
+    }
+}
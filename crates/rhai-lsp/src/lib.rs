@@ -17,6 +17,7 @@ use lsp_async_stub::Server;
 use lsp_types::{notification, request};
 pub use world::{World, WorldState};
 
+pub(crate) mod cache;
 pub(crate) mod config;
 pub(crate) mod diagnostics;
 pub(crate) mod lsp_ext;
@@ -32,16 +33,27 @@ pub fn create_server<E: Environment>() -> Server<World<E>> {
     Server::new()
         .on_request::<request::Initialize, _>(handlers::initialize)
         .on_request::<request::FoldingRangeRequest, _>(handlers::folding_ranges)
+        .on_request::<request::SelectionRangeRequest, _>(handlers::selection_ranges)
         .on_request::<request::GotoDeclaration, _>(handlers::goto_declaration)
         .on_request::<request::GotoDefinition, _>(handlers::goto_definition)
         .on_request::<request::References, _>(handlers::references)
+        .on_request::<request::DocumentHighlightRequest, _>(handlers::document_highlight)
         .on_request::<request::DocumentSymbolRequest, _>(handlers::document_symbols)
+        .on_request::<request::WorkspaceSymbol, _>(handlers::workspace_symbols)
         .on_request::<request::HoverRequest, _>(handlers::hover)
         .on_request::<request::SemanticTokensFullRequest, _>(handlers::semantic_tokens)
         .on_request::<request::Completion, _>(handlers::completion)
+        .on_request::<request::SignatureHelpRequest, _>(handlers::signature_help)
+        .on_request::<request::InlayHintRequest, _>(handlers::inlay_hints)
+        .on_request::<request::CodeActionRequest, _>(handlers::code_action)
+        .on_request::<request::CallHierarchyPrepare, _>(handlers::prepare_call_hierarchy)
+        .on_request::<request::CallHierarchyIncomingCalls, _>(handlers::incoming_calls)
+        .on_request::<request::CallHierarchyOutgoingCalls, _>(handlers::outgoing_calls)
         .on_request::<request::PrepareRenameRequest, _>(handlers::prepare_rename)
         .on_request::<request::Rename, _>(handlers::rename)
         .on_request::<request::Formatting, _>(handlers::format)
+        .on_request::<request::RangeFormatting, _>(handlers::format_range)
+        .on_request::<request::OnTypeFormatting, _>(handlers::format_on_type)
         .on_notification::<notification::Initialized, _>(handlers::initialized)
         .on_notification::<notification::DidOpenTextDocument, _>(handlers::document_open)
         .on_notification::<notification::DidChangeTextDocument, _>(handlers::document_change)
@@ -53,6 +65,7 @@ pub fn create_server<E: Environment>() -> Server<World<E>> {
         .on_request::<lsp_ext::request::HirDump, _>(handlers::hir_dump)
         .on_request::<lsp_ext::request::SyntaxTree, _>(handlers::syntax_tree)
         .on_request::<lsp_ext::request::ConvertOffsets, _>(handlers::convert_offsets)
+        .on_request::<lsp_ext::request::ModuleGraph, _>(handlers::module_graph)
         .build()
 }
 
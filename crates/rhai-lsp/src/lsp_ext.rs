@@ -75,4 +75,50 @@ pub mod request {
 
         const METHOD: &'static str = "rhai/convertOffsets";
     }
+
+    pub enum ModuleGraph {}
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ModuleGraphParams {
+        pub workspace_uri: Option<Url>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct ModuleGraphResult {
+        /// All modules that are not inline.
+        pub modules: Vec<ModuleGraphModule>,
+        /// Resolved imports between the modules.
+        pub imports: Vec<ModuleGraphImport>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ModuleGraphModule {
+        /// The URL of the module or `static`.
+        pub name: String,
+        /// The document that defines the module, if any.
+        pub uri: Option<Url>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ModuleGraphImport {
+        /// Index of the importing module.
+        pub from: usize,
+        /// Index of the imported module.
+        pub to: usize,
+        /// The location of the import statement.
+        pub location: Option<lsp_types::Location>,
+        /// Whether the import is part of an import cycle.
+        pub circular: bool,
+    }
+
+    impl Request for ModuleGraph {
+        type Params = ModuleGraphParams;
+
+        type Result = Option<ModuleGraphResult>;
+
+        const METHOD: &'static str = "rhai/moduleGraph";
+    }
 }
@@ -1,20 +1,25 @@
 use crate::{
+    cache,
     config::{InitConfig, LspConfig},
     utils::Debouncer,
     IndexMap,
 };
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
-use lsp_async_stub::{rpc, util::Mapper};
+use lsp_async_stub::{
+    rpc,
+    util::{Mapper, Range},
+};
 use lsp_types::Url;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use rhai_common::{config::Config, environment::Environment, util::Normalize};
 use rhai_hir::{ty::Type, Hir};
 use rhai_rowan::{
-    parser::{Operator, Parse, Parser},
+    parser::{Operator, Parse, ParseError, Parser},
     util::{is_rhai_def, is_valid_ident},
+    TextSize,
 };
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use std::{collections::HashSet, path::Path, sync::Arc, time::Duration};
 use tokio::sync::RwLock as AsyncRwLock;
 
 pub static DEFAULT_WORKSPACE_URL: Lazy<Url> = Lazy::new(|| Url::parse("root:///").unwrap());
@@ -178,10 +183,18 @@ impl<E: Environment> Workspace<E> {
             tracing::debug!("no config file found");
         }
 
-        self.rhai_config.prepare(&self.env, &root_path)
+        self.rhai_config.prepare(&self.env, &root_path)?;
+        self.hir
+            .set_import_resolver(self.rhai_config.modules.module_resolver());
+
+        Ok(())
     }
 
-    pub(crate) async fn load_all_files(&mut self) {
+    /// Load all the files of the workspace.
+    ///
+    /// If a cache path is given, the HIR is loaded from the cache
+    /// if possible and is cached otherwise.
+    pub(crate) async fn load_all_files(&mut self, cache_path: Option<&Path>) {
         let includes = self.rhai_config.source.include.as_ref().unwrap();
 
         let mut paths = Vec::new();
@@ -210,6 +223,8 @@ impl<E: Environment> Workspace<E> {
 
         tracing::info!(count = all, excluded, "found files");
 
+        let mut files = Vec::with_capacity(paths.len());
+
         for path in paths {
             if self.env.is_dir(&path) {
                 continue;
@@ -234,53 +249,175 @@ impl<E: Environment> Workspace<E> {
                 }
             };
 
+            files.push((document_url, source_text));
+        }
+
+        let cache_file = cache_path.map(|path| cache::cache_file(path, &self.root));
+        let config_hash = cache::config_hash(&self.rhai_config);
+
+        if let Some(cache_file) = &cache_file {
+            if let Some(cached) = cache::load_hir(&self.env, cache_file, config_hash).await {
+                tracing::info!(path = ?cache_file, "using cached HIR");
+
+                if self.load_cached_hir(cached, files) {
+                    self.store_cached_hir(cache_file, config_hash).await;
+                }
+
+                return;
+            }
+        }
+
+        for (document_url, source_text) in files {
             self.add_document(document_url, &source_text);
         }
         self.hir.resolve_all();
+
+        if let Some(cache_file) = &cache_file {
+            self.store_cached_hir(cache_file, config_hash).await;
+        }
+    }
+
+    /// Use a HIR that was built from the given files earlier.
+    ///
+    /// Only files that changed since are parsed and resolved again,
+    /// unchanged files without syntax errors are parsed on first use.
+    ///
+    /// Returns whether any of the files changed.
+    fn load_cached_hir(&mut self, cached: cache::CachedHir, files: Vec<(Url, String)>) -> bool {
+        self.hir = cached.hir;
+        self.hir
+            .set_import_resolver(self.rhai_config.modules.module_resolver());
+
+        // There are no documents yet, so this only
+        // collects the operators for the parser.
+        self.check_operators();
+
+        let operators: Arc<[(String, Operator)]> = self.parser_operators().into();
+
+        let removed = cached
+            .sources
+            .keys()
+            .filter(|url| !files.iter().any(|(file_url, _)| file_url == *url))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut changed = Vec::new();
+
+        for (document_url, source_text) in files {
+            match cached.sources.get(&document_url) {
+                Some(source) if source.hash == cache::source_hash(&source_text) => {
+                    let document = if source.syntax_errors {
+                        self.parse_document(&source_text)
+                    } else {
+                        Document::unparsed(&source_text, operators.clone())
+                    };
+
+                    self.documents.insert(document_url, document);
+                }
+                _ => changed.push((document_url, source_text)),
+            }
+        }
+
+        if removed.is_empty() && changed.is_empty() {
+            return false;
+        }
+
+        tracing::info!(
+            changed = changed.len(),
+            removed = removed.len(),
+            "updating cached HIR"
+        );
+
+        for url in removed {
+            if let Some(src) = self.hir.source_by_url(&url.normalize()) {
+                self.hir.remove_source(src);
+            }
+        }
+
+        for (document_url, source_text) in changed {
+            self.add_document(document_url, &source_text);
+        }
+
+        self.hir.resolve_changed();
+
+        true
+    }
+
+    async fn store_cached_hir(&self, cache_file: &Path, config_hash: u64) {
+        let sources = self
+            .documents
+            .iter()
+            .map(|(url, document)| {
+                (
+                    url.clone(),
+                    cache::CachedSource {
+                        hash: cache::source_hash(document.text()),
+                        syntax_errors: !document.syntax_errors().is_empty(),
+                    },
+                )
+            })
+            .collect();
+
+        cache::store_hir(&self.env, cache_file, config_hash, &sources, &self.hir).await;
     }
 
     pub fn add_document(&mut self, url: Url, text: &str) {
+        let document = self.parse_document(text);
+        let is_def = document.is_def;
+
+        let normalized_url = url.clone().normalize();
+
+        self.hir
+            .add_source(&normalized_url, &document.parse().clone_syntax());
+        self.documents.insert(url, document);
+
+        if is_def {
+            self.check_operators();
+        }
+    }
+
+    fn parse_document(&self, text: &str) -> Document {
         let is_def = is_rhai_def(text);
 
-        let parse = if is_rhai_def(text) {
-            Parser::new(text)
-                .with_operators(self.custom_operators.iter().filter_map(|(name, .., bp)| {
-                    if is_valid_ident(name) {
-                        Some((name.clone(), Operator { binding_power: *bp }))
-                    } else {
-                        None
-                    }
-                }))
-                .parse_def()
+        let parse = if is_def {
+            self.parser(text).parse_def()
         } else {
-            Parser::new(text)
-                .with_operators(self.custom_operators.iter().filter_map(|(name, .., bp)| {
-                    if is_valid_ident(name) {
-                        Some((name.clone(), Operator { binding_power: *bp }))
-                    } else {
-                        None
-                    }
-                }))
-                .parse_script()
+            self.parser(text).parse_script()
         };
 
-        let mapper = Mapper::new_utf16(text, false);
+        Document::parsed(text, parse, is_def)
+    }
 
-        let normalized_url = url.clone().normalize();
+    /// Replace the given range of an existing document with new text.
+    ///
+    /// For scripts only the edited block is parsed again if possible.
+    pub fn edit_document(&mut self, url: &Url, range: Range, new_text: &str) {
+        let Some(doc) = self.documents.get(url) else {
+            tracing::warn!(%url, "edited document does not exist");
+            return;
+        };
 
-        self.hir.add_source(&normalized_url, &parse.clone_syntax());
-        self.documents.insert(
-            url,
-            Document {
-                parse,
-                mapper,
-                is_def,
-            },
-        );
+        let Some(edit) = doc.mapper.text_range(range) else {
+            tracing::warn!(%url, ?range, "invalid edit range");
+            return;
+        };
 
-        if is_def {
-            self.check_operators();
+        let mut text = doc.text().to_string();
+        text.replace_range(std::ops::Range::<usize>::from(edit), new_text);
+
+        if doc.is_def || is_rhai_def(&text) {
+            self.add_document(url.clone(), &text);
+            return;
         }
+
+        let parse = self
+            .parser(&text)
+            .reparse_script(doc.parse(), edit, TextSize::of(new_text));
+
+        self.hir
+            .add_source(&url.clone().normalize(), &parse.clone_syntax());
+        self.documents
+            .insert(url.clone(), Document::parsed(&text, parse, false));
     }
 
     pub fn remove_document(&mut self, uri: &Url) {
@@ -295,6 +432,27 @@ impl<E: Environment> Workspace<E> {
         }
     }
 
+    /// A parser for the given text with all the
+    /// custom operators in the workspace.
+    fn parser<'t>(&self, text: &'t str) -> Parser<'t> {
+        Parser::new(text).with_operators(self.parser_operators())
+    }
+
+    /// The custom operators in the workspace
+    /// that can be used by the parser.
+    fn parser_operators(&self) -> Vec<(String, Operator)> {
+        self.custom_operators
+            .iter()
+            .filter_map(|(name, .., bp)| {
+                if is_valid_ident(name) {
+                    Some((name.clone(), Operator { binding_power: *bp }))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Reparses scripts if the list of defined operators change.
     pub(crate) fn check_operators(&mut self) {
         let new_operators = self
@@ -320,7 +478,7 @@ impl<E: Environment> Workspace<E> {
                     self.hir.remove_source(src);
                 }
 
-                docs_to_reparse.push((uri.clone(), doc.parse.green.to_string()));
+                docs_to_reparse.push((uri.clone(), doc.text().to_string()));
             }
 
             doc.is_def
@@ -332,9 +490,60 @@ impl<E: Environment> Workspace<E> {
     }
 }
 
+/// A document in a workspace.
+///
+/// Documents loaded along with a cached HIR are only parsed on first use,
+/// these are known to have no syntax errors.
 #[derive(Debug, Clone)]
 pub struct Document {
-    pub(crate) parse: Parse,
+    text: Arc<str>,
+    /// Custom operators for parsing the document later.
+    operators: Arc<[(String, Operator)]>,
+    parse: OnceCell<Parse>,
     pub(crate) mapper: Mapper,
     pub(crate) is_def: bool,
 }
+
+impl Document {
+    fn parsed(text: &str, parse: Parse, is_def: bool) -> Self {
+        Self {
+            text: text.into(),
+            operators: Vec::new().into(),
+            parse: OnceCell::with_value(parse),
+            mapper: Mapper::new_utf16(text, false),
+            is_def,
+        }
+    }
+
+    fn unparsed(text: &str, operators: Arc<[(String, Operator)]>) -> Self {
+        Self {
+            text: text.into(),
+            operators,
+            parse: OnceCell::new(),
+            mapper: Mapper::new_utf16(text, false),
+            is_def: is_rhai_def(text),
+        }
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The parsed document, it is parsed if it was not yet.
+    pub(crate) fn parse(&self) -> &Parse {
+        self.parse.get_or_init(|| {
+            let parser = Parser::new(&self.text).with_operators(self.operators.iter().cloned());
+
+            if self.is_def {
+                parser.parse_def()
+            } else {
+                parser.parse_script()
+            }
+        })
+    }
+
+    /// The syntax errors of the document without parsing it.
+    pub(crate) fn syntax_errors(&self) -> &[ParseError] {
+        self.parse.get().map_or(&[], |parse| &parse.errors)
+    }
+}
@@ -11,12 +11,12 @@ fn bench(c: &mut Criterion) {
     const OOP_SRC: &str = include_str!("../../../testdata/valid/oop.rhai");
 
     let mut g = c.benchmark_group("simple");
-    g.throughput(Throughput::Bytes(SIMPLE_SRC.as_bytes().len() as u64))
+    g.throughput(Throughput::Bytes(SIMPLE_SRC.len() as u64))
         .bench_function("parse simple", |b| b.iter(|| parse(black_box(SIMPLE_SRC))));
     g.finish();
 
     let mut g = c.benchmark_group("oop");
-    g.throughput(Throughput::Bytes(OOP_SRC.as_bytes().len() as u64))
+    g.throughput(Throughput::Bytes(OOP_SRC.len() as u64))
         .bench_function("parse oop", |b| b.iter(|| parse(black_box(OOP_SRC))));
     g.finish();
 }
@@ -18,6 +18,6 @@ pub mod query;
 pub mod syntax;
 pub mod util;
 
-pub use rowan::{TextRange, TextSize};
+pub use rowan::{TextRange, TextSize, TokenAtOffset};
 
 pub use parser::{parsers, Parser};
@@ -58,6 +58,14 @@ impl<'src> Context<'src> {
         self.custom_ops.insert(ident, op);
     }
 
+    /// Create a fresh context for the given source
+    /// with the same custom operators.
+    pub(super) fn fork<'s>(&self, source: &'s str) -> Context<'s> {
+        let mut ctx = Context::new(source);
+        ctx.custom_ops.clone_from(&self.custom_ops);
+        ctx
+    }
+
     pub(crate) fn finish(self) -> Parse {
         Parse {
             errors: self.errors,
@@ -289,7 +297,7 @@ impl<'src> Context<'src> {
 }
 
 /// A custom Operator.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Operator {
     pub binding_power: (u8, u8),
 }
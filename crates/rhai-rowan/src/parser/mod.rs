@@ -12,6 +12,7 @@ pub use self::context::{Context, Operator};
 
 mod context;
 pub mod parsers;
+mod reparse;
 
 /// A flexible parser.
 ///
@@ -40,6 +41,7 @@ pub mod parsers;
 /// assert!(parse.errors.is_empty());
 /// ```
 pub struct Parser<'src> {
+    source: &'src str,
     context: Context<'src>,
 }
 
@@ -48,6 +50,7 @@ impl<'src> Parser<'src> {
     #[must_use]
     pub fn new(source: &'src str) -> Self {
         Self {
+            source,
             context: Context::new(source),
         }
     }
@@ -174,3 +177,28 @@ pub enum ParseErrorKind {
     )]
     ExpectedOneOfTokens(Vec<SyntaxKind>),
 }
+
+impl ParseErrorKind {
+    /// All the codes returned by [`ParseErrorKind::code`].
+    pub const CODES: &'static [&'static str] = &[
+        "unexpected-eof",
+        "invalid-input",
+        "unexpected-token",
+        "invalid-string",
+        "expected-token",
+        "expected-one-of-tokens",
+    ];
+
+    /// A stable code that identifies the kind of the error.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedEof => "unexpected-eof",
+            Self::InvalidInput => "invalid-input",
+            Self::UnexpectedToken => "unexpected-token",
+            Self::InvalidOrUnclosedString => "invalid-string",
+            Self::ExpectedToken(_) => "expected-token",
+            Self::ExpectedOneOfTokens(_) => "expected-one-of-tokens",
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Incremental reparsing of scripts.
+//!
+//! Instead of parsing the entire source again after an edit,
+//! only the smallest block expression that contains the edit
+//! is parsed, and the result is spliced into the previous tree.
+
+use rowan::{NodeOrToken, TextRange, TextSize};
+
+use super::{parsers::parse_expr_block, Parse, ParseError, Parser};
+use crate::{
+    syntax::{Lexer, SyntaxKind::*, SyntaxNode},
+    T,
+};
+
+impl Parser<'_> {
+    /// Parse the source as a script while reusing
+    /// a previous parse result where possible.
+    ///
+    /// The source of the parser must be the source of `previous`
+    /// with the text in `edit` replaced by `inserted_len` bytes.
+    ///
+    /// If the edited block cannot be reparsed in isolation with the same
+    /// result as a full parse would produce, the entire source is parsed instead.
+    #[must_use]
+    pub fn reparse_script(
+        self,
+        previous: &Parse,
+        edit: TextRange,
+        inserted_len: TextSize,
+    ) -> Parse {
+        match self.reparse_block(previous, edit, inserted_len) {
+            Some(parse) => parse,
+            None => self.parse_script(),
+        }
+    }
+
+    fn reparse_block(
+        &self,
+        previous: &Parse,
+        edit: TextRange,
+        inserted_len: TextSize,
+    ) -> Option<Parse> {
+        let root = previous.clone_syntax();
+
+        if root.kind() != RHAI {
+            return None;
+        }
+
+        let old_len = root.text_range().len();
+
+        if edit.end() > old_len
+            || u32::from(old_len - edit.len() + inserted_len) as usize != self.source.len()
+        {
+            tracing::debug!("edit does not match the source");
+            return None;
+        }
+
+        let block = reparsable_block(&root, edit)?;
+        let old_range = block.text_range();
+        let new_range = TextRange::new(
+            old_range.start(),
+            old_range.end() - edit.len() + inserted_len,
+        );
+
+        let block_src = self.source.get(std::ops::Range::<usize>::from(new_range))?;
+        let remainder = &self.source[usize::from(new_range.start())..];
+
+        if !lexes_as_prefix(block_src, remainder) {
+            return None;
+        }
+
+        let mut ctx = self.context.fork(block_src);
+        parse_expr_block(&mut ctx);
+        let block_parse = ctx.finish();
+
+        let new_block = block_parse.clone_syntax();
+
+        // The new block must cover the exact same source, and it must be
+        // closed by its own brace, otherwise the parser would have
+        // wanted to look past the end of the block.
+        if new_block.kind() != EXPR_BLOCK
+            || usize::from(new_block.text_range().len()) != block_src.len()
+            || !matches!(
+                new_block.last_child_or_token(),
+                Some(NodeOrToken::Token(t)) if t.kind() == T!["}"]
+            )
+        {
+            return None;
+        }
+
+        let green = block.replace_with(block_parse.green);
+
+        let mut errors: Vec<ParseError> = previous
+            .errors
+            .iter()
+            .filter(|err| err.range.end() <= old_range.start())
+            .cloned()
+            .collect();
+
+        errors.extend(block_parse.errors.into_iter().map(|mut err| {
+            err.range += new_range.start();
+            err
+        }));
+
+        errors.extend(
+            previous
+                .errors
+                .iter()
+                .filter(|err| err.range.start() >= old_range.end())
+                .cloned()
+                .map(|mut err| {
+                    err.range = err.range - old_range.end() + new_range.end();
+                    err
+                }),
+        );
+
+        tracing::trace!(?old_range, ?new_range, "reparsed block");
+
+        Some(Parse { errors, green })
+    }
+}
+
+/// Find the innermost block that strictly contains
+/// the edit between its braces.
+fn reparsable_block(root: &SyntaxNode, edit: TextRange) -> Option<SyntaxNode> {
+    let start = match root.covering_element(edit) {
+        NodeOrToken::Node(n) => n,
+        NodeOrToken::Token(t) => t.parent()?,
+    };
+
+    start.ancestors().find(|node| {
+        let range = node.text_range();
+
+        node.kind() == EXPR_BLOCK
+            && range.start() < edit.start()
+            && edit.end() < range.end()
+            && matches!(node.first_token(), Some(t) if t.kind() == T!["{"])
+            && matches!(
+                node.last_child_or_token(),
+                Some(NodeOrToken::Token(t)) if t.kind() == T!["}"]
+            )
+            && !in_switch_pattern(node)
+    })
+}
+
+/// Switch arm patterns are parsed with a different
+/// context, so blocks within them are not reparsed.
+fn in_switch_pattern(node: &SyntaxNode) -> bool {
+    node.ancestors()
+        .filter(|n| n.kind() == SWITCH_ARM)
+        .filter_map(|arm| {
+            arm.children_with_tokens()
+                .find(|c| c.kind() == T!["=>"])
+                .map(|arrow| arrow.text_range().start())
+        })
+        .any(|arrow_start| node.text_range().start() < arrow_start)
+}
+
+/// Whether the source produces the same tokens on its own
+/// as it does at the start of the given remainder.
+///
+/// This is not the case if a token would continue after the
+/// end of the source, e.g. an unclosed string.
+fn lexes_as_prefix(src: &str, remainder: &str) -> bool {
+    let mut lexer = Lexer::new(src);
+    let mut remainder_lexer = Lexer::new(remainder);
+
+    loop {
+        match (lexer.next(), remainder_lexer.next()) {
+            (None, _) => return true,
+            (Some(a), Some(b)) if a == b && lexer.span() == remainder_lexer.span() => {}
+            _ => return false,
+        }
+    }
+}
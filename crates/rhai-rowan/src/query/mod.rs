@@ -3,7 +3,7 @@
 use rowan::{NodeOrToken, TextSize};
 
 use crate::{
-    ast::{AstNode, Path},
+    ast::{ArgList, AstNode, Path},
     syntax::{SyntaxKind::*, SyntaxNode, SyntaxToken},
     T,
 };
@@ -166,6 +166,35 @@ impl Query {
             .unwrap_or_else(|| p.segments().count())
     }
 
+    /// The argument list of the innermost call
+    /// with the cursor between its parentheses.
+    #[must_use]
+    pub fn arg_list(&self) -> Option<ArgList> {
+        self.before
+            .as_ref()?
+            .syntax
+            .parent_ancestors()
+            .filter_map(ArgList::cast)
+            .find(|arg_list| {
+                arg_list
+                    .punct_paren_start_token()
+                    .is_some_and(|t| t.text_range().end() <= self.offset)
+                    && arg_list
+                        .punct_paren_end_token()
+                        .is_none_or(|t| self.offset <= t.text_range().start())
+            })
+    }
+
+    /// The index of the argument the cursor is at in the given argument list.
+    #[must_use]
+    pub fn arg_index(&self, arg_list: &ArgList) -> usize {
+        arg_list
+            .syntax()
+            .children_with_tokens()
+            .filter(|t| t.kind() == T![","] && t.text_range().end() <= self.offset)
+            .count()
+    }
+
     #[must_use]
     pub fn is_in_comment(&self) -> bool {
         match (&self.before, &self.after) {
@@ -331,3 +331,68 @@ fn test_complete_ref_fails_in_decl() {
         assert!(!q.can_complete_ref(), "test failed for index {idx}",);
     }
 }
+
+#[test]
+fn test_query_arg_list() {
+    let (offset, src) = src_cursor_offset(
+        r"
+            foo(a, bar(1, 2), $$)
+            ",
+    );
+
+    let syntax = Parser::new(&src).parse_script().into_syntax();
+
+    let q = Query::at(&syntax, offset);
+
+    let arg_list = q.arg_list().unwrap();
+    assert!(arg_list.syntax().text().to_string().starts_with("(a, bar"));
+    assert_eq!(q.arg_index(&arg_list), 2);
+}
+
+#[test]
+fn test_query_arg_list_nested() {
+    let (offset, src) = src_cursor_offset(
+        r"
+            foo(a, bar(1, $$2))
+            ",
+    );
+
+    let syntax = Parser::new(&src).parse_script().into_syntax();
+
+    let q = Query::at(&syntax, offset);
+
+    let arg_list = q.arg_list().unwrap();
+    assert_eq!(arg_list.syntax().text().to_string(), "(1, 2)");
+    assert_eq!(q.arg_index(&arg_list), 1);
+}
+
+#[test]
+fn test_query_arg_list_unclosed() {
+    let (offset, src) = src_cursor_offset(
+        r"
+            foo(a,$$
+            ",
+    );
+
+    let syntax = Parser::new(&src).parse_script().into_syntax();
+
+    let q = Query::at(&syntax, offset);
+
+    let arg_list = q.arg_list().unwrap();
+    assert_eq!(q.arg_index(&arg_list), 1);
+}
+
+#[test]
+fn test_query_not_arg_list() {
+    let (offset, src) = src_cursor_offset(
+        r"
+            foo(a, b)$$
+            ",
+    );
+
+    let syntax = Parser::new(&src).parse_script().into_syntax();
+
+    let q = Query::at(&syntax, offset);
+
+    assert!(q.arg_list().is_none());
+}
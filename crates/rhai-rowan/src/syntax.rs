@@ -447,6 +447,12 @@ impl SyntaxKind {
     pub fn is_def(&self) -> bool {
         self >= &SyntaxKind::RHAI_DEF && self <= &SyntaxKind::DEF_FN
     }
+
+    /// Whether the syntax kind is `=` or a compound assignment operator.
+    #[must_use]
+    pub fn is_assign_op(&self) -> bool {
+        self >= &SyntaxKind::OP_ASSIGN && self <= &SyntaxKind::OP_XOR_ASSIGN
+    }
 }
 
 impl From<SyntaxKind> for rowan::SyntaxKind {
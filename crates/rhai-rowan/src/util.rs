@@ -1,6 +1,9 @@
-use crate::parser::{
-    parsers::{self, def::parse_def_header, parse_expr},
-    Parse, Parser,
+use crate::{
+    parser::{
+        parsers::{self, def::parse_def_header, parse_expr},
+        Parse, Parser,
+    },
+    syntax::{SyntaxElement, SyntaxKind::WHITESPACE, SyntaxNode},
 };
 use rowan::{TextRange, TextSize};
 use thiserror::Error;
@@ -99,6 +102,20 @@ pub fn is_valid_ident(text: &str) -> bool {
     ident_parser.finish().errors.is_empty()
 }
 
+/// The range of the node without leading and trailing whitespace.
+#[must_use]
+pub fn trimmed_range(node: &SyntaxNode) -> Option<TextRange> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| token.kind() != WHITESPACE);
+
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+
+    Some(first.text_range().cover(last.text_range()))
+}
+
 #[must_use]
 pub fn unescape(s: &str, termination_char: char) -> (String, Vec<EscapeError>) {
     let mut chars = s.chars().peekable();
@@ -302,3 +319,18 @@ fn test_cursor_offsets() {
             .collect::<Vec<_>>()
     );
 }
+
+#[cfg(test)]
+#[test]
+fn test_trimmed_range() {
+    let src = "  let a = 1;\n\n  // comment\n";
+    let root = Parser::new(src).parse_script().into_syntax();
+
+    assert_eq!(
+        trimmed_range(&root),
+        Some(TextRange::new(TextSize::from(2), TextSize::from(26)))
+    );
+
+    let root = Parser::new("  \n").parse_script().into_syntax();
+    assert_eq!(trimmed_range(&root), None);
+}
@@ -0,0 +1,63 @@
+use rhai_rowan::{parser::Parser, TextRange, TextSize};
+use test_case::test_case;
+
+const FIBONACCI: &str = include_str!("../../../testdata/valid/fibonacci.rhai");
+
+fn edit(src: &str, find: &str, replace_with: &str) -> (String, TextRange, TextSize) {
+    let start = src.find(find).expect("text to replace");
+    let range = TextRange::at(
+        TextSize::from(start as u32),
+        TextSize::from(find.len() as u32),
+    );
+
+    let mut new_src = src.to_string();
+    new_src.replace_range(start..start + find.len(), replace_with);
+
+    (new_src, range, TextSize::from(replace_with.len() as u32))
+}
+
+#[test_case("n-1", "n - 1"; "inside block")]
+#[test_case("n < 2", "n <"; "introduce error")]
+#[test_case("result = fib(TARGET);", "result = fib(TARGET"; "unclosed call")]
+#[test_case("        n\n", "        \"n\n"; "unclosed string")]
+#[test_case("fib(n-1) + fib(n-2)\n    }", "}"; "remove closing brace")]
+#[test_case("const TARGET = 28;", "const TARGET = 29;"; "outside block")]
+#[test_case("ANSWER}!`", "ANSWER}!`  "; "whitespace")]
+fn reparse_same_as_full_parse(find: &str, replace_with: &str) {
+    let previous = Parser::new(FIBONACCI).parse_script();
+    let (src, range, inserted_len) = edit(FIBONACCI, find, replace_with);
+
+    let reparsed = Parser::new(&src).reparse_script(&previous, range, inserted_len);
+    let full = Parser::new(&src).parse_script();
+
+    assert_eq!(reparsed.green, full.green);
+    assert_eq!(reparsed.errors, full.errors);
+    assert_eq!(reparsed.clone_syntax().to_string(), src);
+}
+
+#[test]
+fn reparse_keeps_errors_outside_block() {
+    let src = "let a = ;\nfn foo() {\n    1 + 2\n}\nlet b = ;\n";
+    let previous = Parser::new(src).parse_script();
+    assert!(!previous.errors.is_empty());
+
+    let (src, range, inserted_len) = edit(src, "1 + 2", "1 + 2 + 3");
+    let reparsed = Parser::new(&src).reparse_script(&previous, range, inserted_len);
+    let full = Parser::new(&src).parse_script();
+
+    assert_eq!(reparsed.green, full.green);
+    assert_eq!(reparsed.errors, full.errors);
+}
+
+#[test]
+fn reparse_reuses_unchanged_nodes() {
+    let previous = Parser::new(FIBONACCI).parse_script();
+    let (src, range, inserted_len) = edit(FIBONACCI, "n-1", "n - 1");
+    let reparsed = Parser::new(&src).reparse_script(&previous, range, inserted_len);
+
+    let first_node = |parse: &rhai_rowan::parser::Parse| {
+        parse.green.children().find_map(|c| c.into_node()).unwrap() as *const _
+    };
+
+    assert_eq!(first_node(&previous), first_node(&reparsed));
+}
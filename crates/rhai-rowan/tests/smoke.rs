@@ -136,3 +136,33 @@ fn parse_ambiguous_integer_field_access() {
     parser.execute(parse_expr);
     assert!(parser.finish().errors.is_empty());
 }
+
+#[test]
+fn assignment_operators() {
+    let tokens = |src: &str| {
+        let mut tokens = Vec::new();
+
+        Parser::new(src).execute(|ctx| {
+            while let Some(token) = ctx.token() {
+                tokens.push(token);
+                ctx.eat();
+            }
+        });
+
+        tokens
+    };
+
+    let assign_ops = tokens("= += -= *= /= %= **= >>= <<= &= |= ^=");
+    assert_eq!(assign_ops.len(), 12);
+    assert!(
+        assign_ops.iter().all(|op| op.is_assign_op()),
+        "{assign_ops:?}"
+    );
+
+    let other_ops = tokens("== != <= >= < > + - ** >> << & | ^ => ..=");
+    assert_eq!(other_ops.len(), 16);
+    assert!(
+        !other_ops.iter().any(|op| op.is_assign_op()),
+        "{other_ops:?}"
+    );
+}
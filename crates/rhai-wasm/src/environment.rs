@@ -172,6 +172,7 @@ pub(crate) struct WasmEnvironment {
     js_glob_files: Function,
     js_read_file: Function,
     js_write_file: Function,
+    js_create_dir_all: Function,
     js_sleep: Function,
     js_is_absolute: Function,
     js_cwd: Function,
@@ -207,6 +208,9 @@ impl From<JsValue> for WasmEnvironment {
             js_write_file: js_sys::Reflect::get(&val, &JsValue::from_str("js_write_file"))
                 .unwrap()
                 .into(),
+            js_create_dir_all: js_sys::Reflect::get(&val, &JsValue::from_str("js_create_dir_all"))
+                .unwrap()
+                .into(),
             js_is_absolute: js_sys::Reflect::get(&val, &JsValue::from_str("js_is_absolute"))
                 .unwrap()
                 .into(),
@@ -324,6 +328,17 @@ impl Environment for WasmEnvironment {
             .map_err(|err| anyhow!("{:?}", err))
     }
 
+    async fn create_dir_all(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let path_str = JsValue::from_str(&path.to_string_lossy());
+        let this = JsValue::null();
+        let res: JsValue = self.js_create_dir_all.call1(&this, &path_str).unwrap();
+
+        JsFuture::from(Promise::from(res))
+            .await
+            .map(|_| ())
+            .map_err(|err| anyhow!("{:?}", err))
+    }
+
     fn is_absolute(&self, path: &Path) -> bool {
         let path_str = JsValue::from_str(&path.to_string_lossy());
         let this = JsValue::null();
@@ -32,6 +32,9 @@ process.on("message", async (d: RpcMessage) => {
         isAbsolute: p => path.isAbsolute(p),
         readFile: path => fsPromise.readFile(path),
         writeFile: (path, data) => fsPromise.writeFile(path, data),
+        createDirAll: async path => {
+          await fsPromise.mkdir(path, { recursive: true });
+        },
         stderr: process.stderr,
         stdErrAtty: () => process.stderr.isTTY,
         stdin: process.stdin,
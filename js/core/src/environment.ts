@@ -40,6 +40,10 @@ export interface Environment {
    * Write a file at the given path.
    */
   writeFile: (path: string, data: Uint8Array) => Promise<void>;
+  /**
+   * Create a directory at the given path along with its missing parents.
+   */
+  createDirAll: (path: string) => Promise<void>;
   /**
    * Search a glob file pattern and return the matched files.
    */
@@ -98,6 +102,7 @@ export function convertEnv(env: Environment): any {
     js_sleep: env.sleep,
     js_read_file: env.readFile,
     js_write_file: env.writeFile,
+    js_create_dir_all: env.createDirAll,
   };
 }
 